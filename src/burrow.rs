@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    frame_manager::FrameControl,
+    life::LifeStage,
+    rabbit::Rabbit,
+    world_setup::{VoxelType, WorldMap},
+};
+
+/// A rabbit burrow. Rabbits that share a `home` share the burrow, and a rabbit inside it is safe from predators.
+#[derive(Component)]
+pub struct Burrow {
+    pub location: (i32, i32),
+}
+
+#[derive(Resource)]
+pub struct BurrowAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+/// Chance per tick that a homeless rabbit starts digging where it stands.
+const DIG_CHANCE: f32 = 0.02;
+/// Tiles around a spot searched for other burrows; each one found makes digging there more likely,
+/// so burrows cluster into warrens.
+const COLONY_RADIUS: i32 = 4;
+
+pub(super) fn plugin(app: &mut App) {
+    app
+        .add_systems(Startup, setup_burrow_assets)
+        .add_systems(Update, (dig_burrows, update_sheltered_rabbits));
+}
+
+fn setup_burrow_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(BurrowAssets {
+        mesh: meshes.add(Mesh::from(Cuboid {
+            half_size: Vec3::new(0.35, 0.05, 0.35),
+        })),
+        material: materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(0.2, 0.1, 0.03),
+            ..default()
+        }),
+    });
+}
+
+/// Whether a rabbit could dig a burrow at a location: dry ground with plants close by for cover.
+fn can_dig_at(world_map: &WorldMap, location: (i32, i32)) -> bool {
+    world_map.is_walkable(location)
+        && world_map.burrow_at(location).is_none()
+        && world_map.foliage_at(location).is_none()
+        && !world_map.foliage_within(location, 1).is_empty()
+}
+
+pub fn spawn_burrow(
+    location: (i32, i32),
+    world_map: &mut WorldMap,
+    commands: &mut Commands,
+    burrow_assets: &BurrowAssets,
+) -> Entity {
+    let surface_height = world_map.voxel_type(location).unwrap_or(VoxelType::GrassVoxel).surface_height();
+    let burrow_entity = commands.spawn((
+        Burrow { location },
+        Mesh3d(burrow_assets.mesh.clone()),
+        MeshMaterial3d(burrow_assets.material.clone()),
+        Transform::from_translation(world_map.to_world(location, surface_height)),
+    )).id();
+    world_map.set_burrow(location, Some(burrow_entity));
+    burrow_entity
+}
+
+/// Rabbits without a home now and then dig one under cover where they stand.
+fn dig_burrows(
+    mut commands: Commands,
+    frame_control: Res<FrameControl>,
+    burrow_assets: Res<BurrowAssets>,
+    burrow_query: Query<&Burrow>,
+    mut world_map_query: Query<&mut WorldMap>,
+    mut rabbit_query: Query<&mut Rabbit>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+    for mut rabbit in rabbit_query.iter_mut() {
+        // Kits live in their mother's burrow, or wherever she is, until they are weaned.
+        if rabbit.stage == LifeStage::Kit {
+            continue;
+        }
+        // A burrow that was painted over or erased in the editor no longer counts as a home.
+        if rabbit.home.is_some_and(|home| burrow_query.get(home).is_ok()) {
+            continue;
+        }
+        rabbit.home = None;
+
+        let nearby_burrows = world_map.tiles_within(rabbit.location, COLONY_RADIUS)
+            .filter(|&tile| world_map.burrow_at(tile).is_some())
+            .count();
+        if rng.gen::<f32>() < DIG_CHANCE * (1 + nearby_burrows) as f32 && can_dig_at(&world_map, rabbit.location) {
+            rabbit.home = Some(spawn_burrow(rabbit.location, &mut world_map, &mut commands, &burrow_assets));
+        }
+    }
+}
+
+/// Rabbits that have gone down into their burrow are out of sight.
+fn update_sheltered_rabbits(
+    mut rabbit_query: Query<(&Rabbit, &mut Visibility), Changed<Rabbit>>,
+) {
+    for (rabbit, mut visibility) in rabbit_query.iter_mut() {
+        *visibility = if rabbit.in_burrow {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
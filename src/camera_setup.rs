@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy::input::mouse::MouseMotion;
 
+use crate::editor::EditorState;
+
 pub(super) fn plugin(app: &mut App) {
     app
         .add_systems(Startup, spawn_camera)
@@ -20,9 +22,16 @@ pub fn move_camera(
     input: Res<ButtonInput<KeyCode>>, 
     mut camera_transform_query: Query<&mut Transform, With<Camera3d>>,
     mut mouse_motion: EventReader<MouseMotion>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    editor_state: Res<EditorState>,
 ) {
     let mut camera_transform = camera_transform_query.single_mut();
     for motion in mouse_motion.read() {
+        // The left button paints in editor mode, so only look around while right dragging.
+        if editor_state.enabled && !mouse_buttons.pressed(MouseButton::Right) {
+            continue;
+        }
+
         let yaw = -motion.delta.x * 0.003;
         let pitch = -motion.delta.y * 0.002;
 
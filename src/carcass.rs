@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+
+use crate::{
+    frame_manager::FrameControl,
+    soil::FertiliseSoilEvent,
+    world_setup::{VoxelType, WorldMap},
+};
+
+/// Leaves a carcass with the given amount of meat at a location.
+#[derive(Event)]
+pub struct SpawnCarcassEvent(pub (i32, i32), pub f32);
+
+/// Remains of a dead animal. Scavengers eat the meat, and whatever decomposers take goes back into the soil.
+#[derive(Component)]
+pub struct Carcass {
+    pub location: (i32, i32),
+    pub meat: f32,
+}
+
+impl Carcass {
+    /// Takes up to `amount` of meat from the carcass, returning how much was actually eaten.
+    pub fn scavenge(&mut self, amount: f32) -> f32 {
+        let eaten = self.meat.min(amount);
+        self.meat -= eaten;
+        eaten
+    }
+}
+
+#[derive(Resource)]
+pub struct CarcassAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+/// Meat on the carcass of a full sized animal.
+pub const CARCASS_MEAT: f32 = 10.0;
+/// Meat decomposers take from a carcass each tick.
+const DECAY_RATE: f32 = 0.05;
+/// Soil nutrients released per unit of meat that decomposes; a whole carcass is worth 0.3.
+const NUTRIENTS_PER_MEAT: f32 = 0.03;
+
+pub(super) fn plugin(app: &mut App) {
+    app
+        .add_event::<SpawnCarcassEvent>()
+        .add_systems(Startup, setup_carcass_assets)
+        .add_systems(Update, (spawn_carcasses, decay_carcasses.after(crate::world_setup::update_active_chunks), update_carcass_visuals));
+}
+
+fn setup_carcass_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(CarcassAssets {
+        mesh: meshes.add(Mesh::from(Cuboid {
+            half_size: Vec3::new(0.25, 0.08, 0.15),
+        }).translated_by(Vec3::new(0.0, 0.08, 0.0))),
+        material: materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(0.35, 0.05, 0.05),
+            ..default()
+        }),
+    });
+}
+
+fn spawn_carcasses(
+    mut commands: Commands,
+    mut events: EventReader<SpawnCarcassEvent>,
+    carcass_assets: Res<CarcassAssets>,
+    world_map_query: Query<&WorldMap>,
+) {
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+    for SpawnCarcassEvent(location, meat) in events.read() {
+        let surface_height = world_map.voxel_type(*location).unwrap_or(VoxelType::GrassVoxel).surface_height();
+        commands.spawn((
+            Carcass { location: *location, meat: *meat },
+            Mesh3d(carcass_assets.mesh.clone()),
+            MeshMaterial3d(carcass_assets.material.clone()),
+            Transform::from_translation(world_map.to_world(*location, surface_height)),
+        ));
+    }
+}
+
+fn decay_carcasses(
+    mut commands: Commands,
+    frame_control: Res<FrameControl>,
+    world_map_query: Query<&WorldMap>,
+    mut carcass_query: Query<(Entity, &mut Carcass)>,
+    mut soil_event_writer: EventWriter<FertiliseSoilEvent>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+
+    for (entity, mut carcass) in carcass_query.iter_mut() {
+        let Some(ticks) = world_map.simulated_ticks(carcass.location) else {
+            continue;
+        };
+        let decayed = carcass.scavenge(DECAY_RATE * ticks as f32);
+        if decayed > 0.0 {
+            soil_event_writer.send(FertiliseSoilEvent(carcass.location, decayed * NUTRIENTS_PER_MEAT));
+        }
+        if carcass.meat <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Shrinks carcasses as they are eaten away.
+fn update_carcass_visuals(
+    mut carcass_query: Query<(&Carcass, &mut Transform), Changed<Carcass>>,
+) {
+    for (carcass, mut transform) in carcass_query.iter_mut() {
+        let remaining = (carcass.meat / CARCASS_MEAT).clamp(0.2, 1.0);
+        transform.scale = Vec3::new(remaining, remaining.sqrt(), remaining);
+    }
+}
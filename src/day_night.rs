@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use crate::{frame_manager::FrameControl, scenario::Scenario};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DayPhase {
+    Night,
+    Dawn,
+    Day,
+    Dusk,
+}
+
+/// Time of day in simulation ticks. Tick 0 is midnight.
+#[derive(Resource)]
+pub struct TimeOfDay {
+    pub tick: u32,
+    pub day: u32,
+    pub day_length: u32,
+}
+
+impl TimeOfDay {
+    /// Fraction of the day that has passed, from 0.0 at midnight to 1.0 at the next midnight.
+    pub fn fraction(&self) -> f32 {
+        self.tick as f32 / self.day_length as f32
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        match self.fraction() {
+            f if f < 0.2 => DayPhase::Night,
+            f if f < 0.3 => DayPhase::Dawn,
+            f if f < 0.7 => DayPhase::Day,
+            f if f < 0.8 => DayPhase::Dusk,
+            _ => DayPhase::Night,
+        }
+    }
+
+    pub fn is_night(&self) -> bool {
+        self.phase() == DayPhase::Night
+    }
+
+    /// Height of the sun above the horizon, from -1.0 at midnight to 1.0 at noon.
+    pub fn sun_elevation(&self) -> f32 {
+        ((self.fraction() - 0.25) * TAU).sin()
+    }
+}
+
+#[derive(Component)]
+pub struct Sun;
+
+const NOON_ILLUMINANCE: f32 = light_consts::lux::AMBIENT_DAYLIGHT;
+
+pub(super) fn plugin(app: &mut App) {
+    let day_length = app.world().resource::<Scenario>().day_length.max(1);
+    app
+        .insert_resource(TimeOfDay {
+            tick: day_length / 4,
+            day: 0,
+            day_length,
+        })
+        .add_systems(Startup, spawn_sun)
+        .add_systems(Update, (advance_time_of_day, update_sun).chain());
+}
+
+fn spawn_sun(mut commands: Commands) {
+    commands.spawn((
+        Sun,
+        DirectionalLight {
+            shadows_enabled: true,
+            illuminance: NOON_ILLUMINANCE,
+            ..default()
+        },
+        Transform::default(),
+    ));
+}
+
+fn advance_time_of_day(
+    frame_control: Res<FrameControl>,
+    mut time_of_day: ResMut<TimeOfDay>,
+) {
+    if frame_control.timer.finished() {
+        time_of_day.tick += 1;
+        if time_of_day.tick >= time_of_day.day_length {
+            time_of_day.tick = 0;
+            time_of_day.day += 1;
+        }
+    }
+}
+
+/// Swings the sun east to west over the day, dimming and reddening it towards the horizon.
+fn update_sun(
+    time_of_day: Res<TimeOfDay>,
+    mut ambient_light: ResMut<AmbientLight>,
+    mut sun_query: Query<(&mut DirectionalLight, &mut Transform), With<Sun>>,
+) {
+    let Ok((mut sun, mut transform)) = sun_query.get_single_mut() else {
+        return;
+    };
+
+    let elevation = time_of_day.sun_elevation();
+    let daylight = elevation.max(0.0);
+    // Low sun is warm, high sun is white.
+    let warmth = 1.0 - daylight.sqrt();
+
+    *transform = Transform::from_rotation(
+        Quat::from_rotation_y(time_of_day.fraction() * TAU) * Quat::from_rotation_x(-elevation.asin().clamp(0.05, FRAC_PI_2)),
+    );
+    sun.illuminance = NOON_ILLUMINANCE * daylight;
+    sun.color = Color::linear_rgb(1.0, 1.0 - 0.35 * warmth, 1.0 - 0.7 * warmth);
+
+    // A little moonlight keeps the night readable.
+    ambient_light.color = Color::linear_rgb(0.6 + 0.4 * daylight, 0.7 + 0.3 * daylight, 1.0);
+    ambient_light.brightness = 80.0 + 420.0 * daylight;
+}
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    frame_manager::FrameControl,
+    health::Health,
+    rabbit::{Rabbit, RabbitAssets},
+    scenario::Scenario,
+};
+
+/// Where an animal is in the course of the disease. Each stage counts down the ticks it has left.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DiseaseState {
+    Susceptible,
+    /// Infected but not yet infectious.
+    Exposed(u32),
+    Infected(u32),
+    /// Immune until the count runs out.
+    Recovered(u32),
+}
+
+/// Disease parameters, set from the scenario's `disease` section.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct DiseaseConfig {
+    pub enabled: bool,
+    /// Rabbits infected once the first population has been placed.
+    pub initial_infected: u32,
+    /// Chance per tick that one infectious neighbour passes the disease on to a rabbit with no resistance.
+    pub transmission_chance: f32,
+    /// Tiles over which the disease spreads, within a rabbit's sight.
+    pub transmission_radius: i32,
+    /// Ticks from exposure to becoming infectious. 0 skips straight to infected, giving an SIR model.
+    pub incubation_ticks: u32,
+    /// Ticks an infection lasts.
+    pub infectious_ticks: u32,
+    /// Health an infected rabbit loses each tick, enough to kill one already weakened by hunger or thirst.
+    pub damage: f32,
+    /// Ticks of immunity after recovering. 0 means immunity for life.
+    pub immunity_ticks: u32,
+}
+
+impl Default for DiseaseConfig {
+    fn default() -> Self {
+        DiseaseConfig {
+            enabled: true,
+            initial_infected: 2,
+            transmission_chance: 0.05,
+            transmission_radius: 1,
+            incubation_ticks: 40,
+            infectious_ticks: 120,
+            damage: 0.6,
+            immunity_ticks: 800,
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let disease_config = app.world().resource::<Scenario>().disease.clone();
+    app
+        .insert_resource(disease_config)
+        .add_systems(Update, (seed_disease, spread_disease, progress_disease, update_infected_coats).chain());
+}
+
+/// Infects a few rabbits of the first population.
+fn seed_disease(
+    disease_config: Res<DiseaseConfig>,
+    mut seeded: Local<bool>,
+    mut rabbit_query: Query<&mut Rabbit>,
+) {
+    if *seeded || !disease_config.enabled || rabbit_query.is_empty() {
+        return;
+    }
+    *seeded = true;
+
+    let mut rng = rand::thread_rng();
+    for mut rabbit in rabbit_query.iter_mut().choose_multiple(&mut rng, disease_config.initial_infected as usize) {
+        rabbit.disease = DiseaseState::Infected(disease_config.infectious_ticks);
+    }
+}
+
+fn spread_disease(
+    frame_control: Res<FrameControl>,
+    disease_config: Res<DiseaseConfig>,
+    mut rabbit_query: Query<(Entity, &mut Rabbit)>,
+) {
+    if !frame_control.timer.finished() || !disease_config.enabled {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut exposed = Vec::new();
+
+    for (rabbit_entity, rabbit) in rabbit_query.iter() {
+        if rabbit.disease != DiseaseState::Susceptible {
+            continue;
+        }
+        let infectious_neighbours = rabbit.neighbours_in_range.iter()
+            .filter_map(|neighbour_entity| rabbit_query.get(*neighbour_entity).ok())
+            .filter(|(_, neighbour)| {
+                matches!(neighbour.disease, DiseaseState::Infected(_))
+                    && (neighbour.location.0 - rabbit.location.0).abs() <= disease_config.transmission_radius
+                    && (neighbour.location.1 - rabbit.location.1).abs() <= disease_config.transmission_radius
+            })
+            .count();
+        if infectious_neighbours == 0 {
+            continue;
+        }
+
+        // Each infectious neighbour is an independent chance of catching it.
+        let escape_chance = 1.0 - disease_config.transmission_chance * (1.0 - rabbit.disease_resistance);
+        if rng.gen::<f32>() >= escape_chance.powi(infectious_neighbours as i32) {
+            exposed.push(rabbit_entity);
+        }
+    }
+
+    for rabbit_entity in exposed {
+        if let Ok((_, mut rabbit)) = rabbit_query.get_mut(rabbit_entity) {
+            rabbit.disease = if disease_config.incubation_ticks > 0 {
+                DiseaseState::Exposed(disease_config.incubation_ticks)
+            } else {
+                DiseaseState::Infected(disease_config.infectious_ticks)
+            };
+        }
+    }
+}
+
+fn progress_disease(
+    frame_control: Res<FrameControl>,
+    disease_config: Res<DiseaseConfig>,
+    mut rabbit_query: Query<(&mut Rabbit, &mut Health)>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+
+    for (mut rabbit, mut health) in rabbit_query.iter_mut() {
+        if matches!(rabbit.disease, DiseaseState::Infected(_)) {
+            health.damage(disease_config.damage);
+        }
+        rabbit.disease = match rabbit.disease {
+            DiseaseState::Susceptible => continue,
+            DiseaseState::Exposed(0) => DiseaseState::Infected(disease_config.infectious_ticks),
+            DiseaseState::Exposed(ticks) => DiseaseState::Exposed(ticks - 1),
+            DiseaseState::Infected(0) => DiseaseState::Recovered(disease_config.immunity_ticks),
+            DiseaseState::Infected(ticks) => DiseaseState::Infected(ticks - 1),
+            // Lifelong immunity never counts down.
+            DiseaseState::Recovered(_) if disease_config.immunity_ticks == 0 => continue,
+            DiseaseState::Recovered(0) => DiseaseState::Susceptible,
+            DiseaseState::Recovered(ticks) => DiseaseState::Recovered(ticks - 1),
+        };
+    }
+}
+
+/// Tints infected rabbits so an outbreak can be followed on screen.
+fn update_infected_coats(
+    rabbit_assets: Res<RabbitAssets>,
+    mut rabbit_query: Query<(&Rabbit, &mut MeshMaterial3d<StandardMaterial>), Changed<Rabbit>>,
+) {
+    for (rabbit, mut material) in rabbit_query.iter_mut() {
+        let coat = match rabbit.disease {
+            DiseaseState::Infected(_) => &rabbit_assets.infected_coats[rabbit.coat],
+            _ => &rabbit_assets.coats[rabbit.coat],
+        };
+        if material.0 != *coat {
+            material.0 = coat.clone();
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    foliage::{spawn_single_foliage, FoliageAssets, PlantSpecies, MAX_BIOMASS},
+    fish::{spawn_fish, Fish, FishAssets},
+    frame_manager::FrameControl,
+    hawk::{spawn_hawk, Hawk, HawkAssets},
+    life::LifeConfig,
+    movement::MovementConfig,
+    rabbit::{spawn_rabbit, Rabbit, RabbitAssets, RabbitResource, UpdateNearbyResourcesEvent},
+    weather::Weather,
+    world_setup::{VoxelType, WorldMap},
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EditorTool {
+    Paint(VoxelType),
+    PlaceFoliage,
+    RemoveFoliage,
+    PlaceRabbit,
+    PlaceHawk,
+    PlaceFish,
+    Erase,
+}
+
+/// Settings of the in-app terrain and population editor.
+#[derive(Resource)]
+pub struct EditorState {
+    pub enabled: bool,
+    pub tool: EditorTool,
+    pub brush_radius: i32,
+    /// Species planted with `EditorTool::PlaceFoliage`.
+    pub foliage_species: PlantSpecies,
+    /// Traits given to rabbits dropped with `EditorTool::PlaceRabbit`.
+    pub rabbit_sight_distance: u32,
+    pub rabbit_age: u32,
+    pub rabbit_body_size: f32,
+    pub rabbit_speed: f32,
+    /// Grid location under the mouse cursor, if it is over the world.
+    pub cursor: Option<(i32, i32)>,
+}
+
+impl Default for EditorState {
+    fn default() -> Self {
+        EditorState {
+            enabled: false,
+            tool: EditorTool::Paint(VoxelType::GrassVoxel),
+            brush_radius: 1,
+            foliage_species: PlantSpecies::Grass,
+            rabbit_sight_distance: 3,
+            rabbit_age: 20,
+            rabbit_body_size: 0.25,
+            rabbit_speed: 1.0,
+            cursor: None,
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app
+        .init_resource::<EditorState>()
+        .add_systems(Update, (editor_ui, (update_editor_cursor, apply_editor_tool, draw_editor_brush).chain()));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn editor_ui(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut editor_state: ResMut<EditorState>,
+    mut frame_control: ResMut<FrameControl>,
+    mut movement_config: ResMut<MovementConfig>,
+    mut weather: ResMut<Weather>,
+    mut world_map_query: Query<&mut WorldMap>,
+    rabbit_query: Query<(Entity, &Rabbit)>,
+    mut event_writer: EventWriter<UpdateNearbyResourcesEvent>,
+) {
+    egui::Window::new("Editor").show(contexts.ctx_mut(), |ui| {
+        ui.checkbox(&mut editor_state.enabled, "Editor mode (right drag to look around)");
+
+        let mut paused = frame_control.timer.paused();
+        if ui.checkbox(&mut paused, "Pause simulation").changed() {
+            if paused {
+                frame_control.timer.pause();
+            } else {
+                frame_control.timer.unpause();
+            }
+        }
+
+        ui.checkbox(&mut movement_config.draw_history, "Show movement trails");
+
+        if !editor_state.enabled {
+            return;
+        }
+
+        ui.separator();
+        ui.label("Tool");
+        for (tool, label) in [
+            (EditorTool::Paint(VoxelType::GrassVoxel), "Paint grass"),
+            (EditorTool::Paint(VoxelType::SandVoxel), "Paint sand"),
+            (EditorTool::Paint(VoxelType::ForestVoxel), "Paint forest"),
+            (EditorTool::Paint(VoxelType::WaterVoxel), "Paint water"),
+            (EditorTool::PlaceFoliage, "Place foliage"),
+            (EditorTool::RemoveFoliage, "Remove foliage"),
+            (EditorTool::PlaceRabbit, "Place rabbit"),
+            (EditorTool::PlaceHawk, "Place hawk"),
+            (EditorTool::PlaceFish, "Place fish"),
+            (EditorTool::Erase, "Erase animals, foliage and burrows"),
+        ] {
+            ui.radio_value(&mut editor_state.tool, tool, label);
+        }
+
+        ui.add(egui::Slider::new(&mut editor_state.brush_radius, 0..=10).text("Brush radius"));
+
+        if let EditorTool::Paint(voxel_type) = editor_state.tool {
+            if ui.button("Fill world").clicked() {
+                let mut world_map = world_map_query.single_mut();
+                for x in 0..world_map.width {
+                    for z in 0..world_map.height {
+                        paint_tile((x, z), voxel_type, &mut world_map, &mut weather, &mut commands, &rabbit_query);
+                    }
+                }
+                for (rabbit_entity, _) in rabbit_query.iter() {
+                    event_writer.send(UpdateNearbyResourcesEvent(rabbit_entity));
+                }
+            }
+        }
+
+        if editor_state.tool == EditorTool::PlaceFoliage {
+            ui.separator();
+            ui.label("Species");
+            for species in PlantSpecies::ALL {
+                ui.radio_value(&mut editor_state.foliage_species, species, format!("{:?}", species));
+            }
+        }
+
+        if editor_state.tool == EditorTool::PlaceRabbit {
+            ui.separator();
+            ui.label("Rabbit traits");
+            ui.add(egui::Slider::new(&mut editor_state.rabbit_sight_distance, 1..=10).text("Sight distance"));
+            ui.add(egui::Slider::new(&mut editor_state.rabbit_age, 0..=99).text("Age"));
+            ui.add(egui::Slider::new(&mut editor_state.rabbit_body_size, 0.1..=0.4).text("Body size"));
+            ui.add(egui::Slider::new(&mut editor_state.rabbit_speed, 0.5..=1.5).text("Speed"));
+        }
+
+        if let Some(cursor) = editor_state.cursor {
+            let world_map = world_map_query.single();
+            ui.label(format!(
+                "Cursor: {:?}, moisture {:.2}, fertility {:.2}",
+                cursor, world_map.moisture(cursor), world_map.fertility(cursor),
+            ));
+            if world_map.is_swimmable(cursor) {
+                ui.label(format!("Algae {:.2}", world_map.algae(cursor)));
+            }
+        }
+    });
+}
+
+/// Casts a ray from the camera through the mouse cursor onto the ground plane.
+fn update_editor_cursor(
+    mut editor_state: ResMut<EditorState>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    world_map_query: Query<&WorldMap>,
+) {
+    editor_state.cursor = None;
+    if !editor_state.enabled {
+        return;
+    }
+
+    let (Ok(window), Ok((camera, camera_transform)), Ok(world_map)) =
+        (window_query.get_single(), camera_query.get_single(), world_map_query.get_single()) else {
+        return;
+    };
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
+        return;
+    };
+
+    let ground = Vec3::new(0.0, VoxelType::GrassVoxel.surface_height(), 0.0);
+    let Some(distance) = ray.intersect_plane(ground, InfinitePlane3d::new(Vec3::Y)) else {
+        return;
+    };
+
+    let location = world_map.to_grid(ray.get_point(distance));
+    if world_map.in_bounds(location) {
+        editor_state.cursor = Some(location);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_editor_tool(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    editor_state: Res<EditorState>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    foliage_assets: Res<FoliageAssets>,
+    rabbit_assets: Res<RabbitAssets>,
+    hawk_assets: Res<HawkAssets>,
+    fish_assets: Res<FishAssets>,
+    life_config: Res<LifeConfig>,
+    mut rabbit_resource: ResMut<RabbitResource>,
+    mut weather: ResMut<Weather>,
+    mut world_map_query: Query<&mut WorldMap>,
+    rabbit_query: Query<(Entity, &Rabbit)>,
+    hawk_query: Query<(Entity, &Hawk)>,
+    fish_query: Query<(Entity, &Fish)>,
+    mut event_writer: EventWriter<UpdateNearbyResourcesEvent>,
+) {
+    let Some(cursor) = editor_state.cursor else {
+        return;
+    };
+    if contexts.ctx_mut().is_pointer_over_area() {
+        return;
+    }
+    // Animals are dropped one per click, everything else paints while the button is held.
+    let applying = match editor_state.tool {
+        EditorTool::PlaceRabbit | EditorTool::PlaceHawk | EditorTool::PlaceFish => mouse_buttons.just_pressed(MouseButton::Left),
+        _ => mouse_buttons.pressed(MouseButton::Left),
+    };
+    if !applying {
+        return;
+    }
+
+    let mut world_map = world_map_query.single_mut();
+    let brush: Vec<(i32, i32)> = world_map.tiles_within(cursor, editor_state.brush_radius).collect();
+
+    match editor_state.tool {
+        EditorTool::Paint(voxel_type) => {
+            for location in brush {
+                paint_tile(location, voxel_type, &mut world_map, &mut weather, &mut commands, &rabbit_query);
+            }
+        }
+        EditorTool::PlaceFoliage => {
+            for location in brush {
+                if world_map.voxel_type(location) == Some(editor_state.foliage_species.preferred_biome()) && world_map.foliage_at(location).is_none() {
+                    spawn_single_foliage(location, editor_state.foliage_species, MAX_BIOMASS, &mut world_map, &mut commands, &foliage_assets);
+                }
+            }
+        }
+        EditorTool::RemoveFoliage | EditorTool::Erase => {
+            for location in brush.iter() {
+                if let Some(foliage_entity) = world_map.foliage_at(*location) {
+                    commands.entity(foliage_entity).despawn();
+                    world_map.set_foliage(*location, None);
+                }
+            }
+            if editor_state.tool == EditorTool::Erase {
+                for location in brush.iter() {
+                    if let Some(burrow_entity) = world_map.burrow_at(*location) {
+                        commands.entity(burrow_entity).despawn();
+                        world_map.set_burrow(*location, None);
+                    }
+                }
+                for (rabbit_entity, rabbit) in rabbit_query.iter() {
+                    if brush.contains(&rabbit.location) {
+                        rabbit_resource.rabbits.retain(|&x| x != rabbit_entity);
+                        commands.entity(rabbit_entity).despawn();
+                    }
+                }
+                for (hawk_entity, hawk) in hawk_query.iter() {
+                    if brush.contains(&hawk.location) {
+                        commands.entity(hawk_entity).despawn();
+                    }
+                }
+                for (fish_entity, fish) in fish_query.iter() {
+                    if brush.contains(&fish.location) {
+                        commands.entity(fish_entity).despawn();
+                    }
+                }
+            }
+        }
+        EditorTool::PlaceRabbit => {
+            if !world_map.is_walkable(cursor) {
+                return;
+            }
+            let mut rabbit = Rabbit::new(rabbit_resource.rabbits.len() as u32, cursor);
+            rabbit.sight_distance = editor_state.rabbit_sight_distance;
+            rabbit.set_age(editor_state.rabbit_age, &life_config);
+            rabbit.body_size = Vec3::splat(editor_state.rabbit_body_size);
+            rabbit.speed = editor_state.rabbit_speed;
+            let rabbit_entity = spawn_rabbit(&mut commands, &rabbit_assets, &world_map, rabbit);
+            rabbit_resource.rabbits.push(rabbit_entity);
+            event_writer.send(UpdateNearbyResourcesEvent(rabbit_entity));
+        }
+        EditorTool::PlaceHawk => {
+            // Hawks fly, so they can be released over any terrain.
+            spawn_hawk(cursor, &world_map, &mut commands, &hawk_assets);
+        }
+        EditorTool::PlaceFish => {
+            if !world_map.is_swimmable(cursor) {
+                return;
+            }
+            spawn_fish(cursor, 100.0, &world_map, &mut commands, &fish_assets);
+        }
+    }
+
+    // Rabbits that can see the edited tiles have stale plant and water lists.
+    for (rabbit_entity, rabbit) in rabbit_query.iter() {
+        let reach = editor_state.brush_radius + rabbit.sight();
+        if (rabbit.location.0 - cursor.0).abs() <= reach && (rabbit.location.1 - cursor.1).abs() <= reach {
+            event_writer.send(UpdateNearbyResourcesEvent(rabbit_entity));
+        }
+    }
+}
+
+/// Paints a single tile, clearing away plants and burrows that can't stay on the new terrain. The painted
+/// terrain is kept: a puddle painted over won't drain and a dried tile painted over won't refill.
+fn paint_tile(
+    location: (i32, i32),
+    voxel_type: VoxelType,
+    world_map: &mut WorldMap,
+    weather: &mut Weather,
+    commands: &mut Commands,
+    rabbit_query: &Query<(Entity, &Rabbit)>,
+) {
+    if voxel_type == VoxelType::WaterVoxel {
+        // Leave tiles with rabbits on them walkable rather than drowning the rabbits.
+        if rabbit_query.iter().any(|(_, rabbit)| rabbit.location == location) {
+            return;
+        }
+        if let Some(foliage_entity) = world_map.foliage_at(location) {
+            commands.entity(foliage_entity).despawn();
+            world_map.set_foliage(location, None);
+        }
+        if let Some(burrow_entity) = world_map.burrow_at(location) {
+            commands.entity(burrow_entity).despawn();
+            world_map.set_burrow(location, None);
+        }
+    }
+    world_map.set_voxel_type(location, voxel_type);
+    world_map.set_fertility(location, voxel_type.base_fertility());
+    weather.forget(location);
+}
+
+fn draw_editor_brush(
+    editor_state: Res<EditorState>,
+    world_map_query: Query<&WorldMap>,
+    mut gizmos: Gizmos,
+) {
+    let (Some(cursor), Ok(world_map)) = (editor_state.cursor, world_map_query.get_single()) else {
+        return;
+    };
+    let size = (editor_state.brush_radius * 2 + 1) as f32;
+    let centre = world_map.to_world(cursor, VoxelType::GrassVoxel.surface_height() + 0.05);
+    gizmos.cuboid(
+        Transform::from_translation(centre).with_scale(Vec3::new(size, 0.1, size)),
+        Color::WHITE,
+    );
+}
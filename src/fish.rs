@@ -0,0 +1,347 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    frame_manager::FrameControl,
+    health::Health,
+    movement::{MovementConfig, MovementTween},
+    scenario::Scenario,
+    world_setup::{ChunkLoader, VoxelType, WorldMap, WorldMapDataSetEvent},
+};
+
+/// Fish and algae settings, set from the scenario's `fish` section.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct FishConfig {
+    /// Fish released into the water once the world has been built.
+    pub initial_count: u32,
+    /// No more fish hatch once there are this many.
+    pub max_population: usize,
+    /// Algae every water tile starts with.
+    pub initial_algae: f32,
+    /// Logistic growth rate of algae per tick; thin algae spreads slowly and dense algae levels off.
+    pub algae_growth: f32,
+    /// Algae a fish grazes from its tile each tick.
+    pub algae_bite: f32,
+    /// Hunger restored per unit of algae eaten.
+    pub algae_nutrition: f32,
+    /// Hunger lost per tick.
+    pub hunger_decay: f32,
+    /// Fish at least this well fed can spawn.
+    pub breeding_hunger: f32,
+    /// Ticks a fish waits after spawning before it can spawn again.
+    pub breeding_cooldown: u32,
+}
+
+impl Default for FishConfig {
+    fn default() -> Self {
+        FishConfig {
+            initial_count: 30,
+            max_population: 200,
+            initial_algae: 0.5,
+            algae_growth: 0.02,
+            algae_bite: 0.05,
+            algae_nutrition: 100.0,
+            hunger_decay: 0.5,
+            breeding_hunger: 70.0,
+            breeding_cooldown: 150,
+        }
+    }
+}
+
+/// A fish. Fish only ever swim through water tiles connected to the one they are in.
+#[derive(Component)]
+pub struct Fish {
+    pub location: (i32, i32),
+    /// From 0.0 (starving) to `MAX_FISH_HUNGER`.
+    pub hunger: f32,
+    pub breeding_cooldown: u32,
+    /// Set when a hawk snatches the fish, which is then eaten rather than left to rot.
+    pub caught: bool,
+}
+
+#[derive(Resource)]
+pub struct FishAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+const MAX_FISH_HUNGER: f32 = 100.0;
+/// Hunger a newly hatched fish starts with.
+const FRY_HUNGER: f32 = 50.0;
+/// Hunger each parent spends on spawning.
+const BREEDING_COST: f32 = 20.0;
+/// Health lost per tick by a starving fish.
+const STARVATION_DAMAGE: f32 = 2.0;
+/// Health lost per tick by a fish left on dry land after its water dried up or was filled in.
+const STRANDED_DAMAGE: f32 = 10.0;
+/// Health regained per tick by a well fed fish.
+const FISH_HEALTH_REGEN: f32 = 0.5;
+/// Algae a dead fish returns to the water as it rots.
+const ROTTING_ALGAE: f32 = 0.2;
+/// Meat on a fish, for the hawk that catches it.
+pub const FISH_MEAT: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    let fish_config = app.world().resource::<Scenario>().fish.clone();
+    app
+        .insert_resource(fish_config)
+        .add_systems(Startup, setup_fish_assets)
+        .add_systems(Update, (
+            spawn_initial_fish,
+            grow_algae.after(crate::world_setup::update_active_chunks),
+            (fish_movement, fish_health, breed_fish, remove_dead_fish).chain(),
+        ));
+}
+
+fn setup_fish_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(FishAssets {
+        mesh: meshes.add(Cuboid::new(0.12, 0.08, 0.3)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(1.0, 0.45, 0.1),
+            ..default()
+        }),
+    });
+}
+
+/// Where a fish is drawn, just breaking the surface of the water.
+fn fish_position(location: (i32, i32), world_map: &WorldMap) -> Vec3 {
+    world_map.to_world(location, VoxelType::WaterVoxel.surface_height() + 0.02)
+}
+
+pub fn spawn_fish(
+    location: (i32, i32),
+    hunger: f32,
+    world_map: &WorldMap,
+    commands: &mut Commands,
+    fish_assets: &FishAssets,
+) -> Entity {
+    let position = fish_position(location, world_map);
+    commands.spawn((
+        Fish {
+            location,
+            hunger,
+            breeding_cooldown: 0,
+            caught: false,
+        },
+        Health::default(),
+        Mesh3d(fish_assets.mesh.clone()),
+        MeshMaterial3d(fish_assets.material.clone()),
+        Transform::from_translation(position),
+        MovementTween::new(position),
+        ChunkLoader,
+    )).id()
+}
+
+fn spawn_initial_fish(
+    mut commands: Commands,
+    mut events: EventReader<WorldMapDataSetEvent>,
+    fish_config: Res<FishConfig>,
+    fish_assets: Res<FishAssets>,
+    mut world_map_query: Query<&mut WorldMap>,
+) {
+    for _ in events.read() {
+        let Ok(mut world_map) = world_map_query.get_single_mut() else {
+            continue;
+        };
+
+        let mut water = Vec::new();
+        for x in 0..world_map.width {
+            for z in 0..world_map.height {
+                if world_map.is_swimmable((x, z)) {
+                    world_map.set_algae((x, z), fish_config.initial_algae);
+                    water.push((x, z));
+                }
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        for &location in water.choose_multiple(&mut rng, fish_config.initial_count as usize) {
+            spawn_fish(location, MAX_FISH_HUNGER, &world_map, &mut commands, &fish_assets);
+        }
+    }
+}
+
+/// Grows algae on the water tiles of simulated chunks.
+fn grow_algae(
+    frame_control: Res<FrameControl>,
+    fish_config: Res<FishConfig>,
+    mut world_map_query: Query<&mut WorldMap>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    for chunk_index in 0..world_map.chunks.len() {
+        if !world_map.chunks[chunk_index].active {
+            continue;
+        }
+        let tiles: Vec<(i32, i32)> = world_map.chunk_tiles(chunk_index).collect();
+        for location in tiles {
+            let Some(ticks) = world_map.simulated_ticks(location) else {
+                continue;
+            };
+            if !world_map.is_swimmable(location) {
+                continue;
+            }
+            // A trace of algae drifts into even clear water, so grazed-out tiles recover.
+            let algae = world_map.algae(location);
+            let growth = fish_config.algae_growth * ticks as f32 * (algae + 0.05) * (1.0 - algae);
+            world_map.set_algae(location, algae + growth);
+        }
+    }
+}
+
+/// Hungry fish graze the algae where they are or swim to the richest neighbouring water; fed fish wander.
+fn fish_movement(
+    frame_control: Res<FrameControl>,
+    fish_config: Res<FishConfig>,
+    movement_config: Res<MovementConfig>,
+    mut world_map_query: Query<&mut WorldMap>,
+    mut fish_query: Query<(&mut Fish, &mut MovementTween)>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+    for (mut fish, mut tween) in fish_query.iter_mut() {
+        fish.hunger = (fish.hunger - fish_config.hunger_decay).max(0.0);
+        fish.breeding_cooldown = fish.breeding_cooldown.saturating_sub(1);
+
+        let moves = movement_config.swim_neighbours(&world_map, fish.location);
+        let here = world_map.algae(fish.location);
+        let next = if fish.hunger < fish_config.breeding_hunger {
+            moves.iter()
+                .copied()
+                .max_by(|a, b| world_map.algae(*a).total_cmp(&world_map.algae(*b)))
+                .filter(|&tile| world_map.algae(tile) > here + fish_config.algae_bite)
+        } else if rng.gen_bool(0.5) {
+            moves.choose(&mut rng).copied()
+        } else {
+            None
+        };
+
+        match next {
+            Some(tile) => {
+                fish.location = tile;
+                tween.move_to(fish_position(tile, &world_map), 0);
+            }
+            None if world_map.is_swimmable(fish.location) && fish.hunger < MAX_FISH_HUNGER => {
+                let eaten = here.min(fish_config.algae_bite);
+                world_map.set_algae(fish.location, here - eaten);
+                fish.hunger = (fish.hunger + eaten * fish_config.algae_nutrition).min(MAX_FISH_HUNGER);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Starving or stranded fish lose health, and well fed ones recover.
+fn fish_health(
+    frame_control: Res<FrameControl>,
+    fish_config: Res<FishConfig>,
+    world_map_query: Query<&WorldMap>,
+    mut fish_query: Query<(&Fish, &mut Health)>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+    for (fish, mut health) in fish_query.iter_mut() {
+        if !world_map.is_swimmable(fish.location) {
+            health.damage(STRANDED_DAMAGE);
+        } else if fish.hunger <= 0.0 {
+            health.damage(STARVATION_DAMAGE);
+        } else if fish.hunger >= fish_config.breeding_hunger {
+            health.heal(FISH_HEALTH_REGEN);
+        }
+    }
+}
+
+/// Well fed fish that meet spawn together, and a fry hatches in the water next to them.
+fn breed_fish(
+    mut commands: Commands,
+    frame_control: Res<FrameControl>,
+    fish_config: Res<FishConfig>,
+    fish_assets: Res<FishAssets>,
+    movement_config: Res<MovementConfig>,
+    world_map_query: Query<&WorldMap>,
+    mut fish_query: Query<(Entity, &mut Fish)>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+
+    let mut population = fish_query.iter().count();
+    let ready: Vec<(Entity, (i32, i32))> = fish_query.iter()
+        .filter(|(_, fish)| fish.hunger >= fish_config.breeding_hunger && fish.breeding_cooldown == 0)
+        .map(|(entity, fish)| (entity, fish.location))
+        .collect();
+
+    let mut rng = rand::thread_rng();
+    let mut spawned: Vec<Entity> = Vec::new();
+    for (index, &(entity, location)) in ready.iter().enumerate() {
+        if population >= fish_config.max_population {
+            break;
+        }
+        if spawned.contains(&entity) {
+            continue;
+        }
+        let Some(&(partner, _)) = ready[index + 1..].iter()
+            .find(|(other, other_location)| !spawned.contains(other) && movement_config.distance(location, *other_location) <= 1) else {
+            continue;
+        };
+        let Some(&hatchery) = movement_config.swim_neighbours(world_map, location).choose(&mut rng) else {
+            continue;
+        };
+
+        for parent in [entity, partner] {
+            if let Ok((_, mut fish)) = fish_query.get_mut(parent) {
+                fish.hunger -= BREEDING_COST;
+                fish.breeding_cooldown = fish_config.breeding_cooldown;
+            }
+        }
+        spawned.extend([entity, partner]);
+        spawn_fish(hatchery, FRY_HUNGER, world_map, &mut commands, &fish_assets);
+        population += 1;
+    }
+}
+
+/// Dead fish sink and rot, feeding the algae where they went down, unless a hawk carried them off.
+fn remove_dead_fish(
+    mut commands: Commands,
+    mut world_map_query: Query<&mut WorldMap>,
+    fish_query: Query<(Entity, &Fish, &Health), Changed<Health>>,
+) {
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+    for (fish_entity, fish, health) in fish_query.iter() {
+        if !health.is_dead() {
+            continue;
+        }
+        commands.entity(fish_entity).despawn();
+        if !fish.caught && world_map.is_swimmable(fish.location) {
+            let algae = world_map.algae(fish.location);
+            world_map.set_algae(fish.location, algae + ROTTING_ALGAE);
+        }
+    }
+}
@@ -1,57 +1,266 @@
 use bevy::prelude::*;
+use rand::Rng;
 
 use crate::{
-    world_setup::{Voxel, VoxelType,  VoxelsSpawnedEvent},
-    frame_manager::FrameControl
+    world_setup::{VoxelType, WorldMap, WorldMapDataSetEvent},
+    frame_manager::FrameControl,
+    seasons::SeasonCycle,
 };
 
+/// Most edible biomass a plant can hold.
+pub const MAX_BIOMASS: f32 = 10.0;
+/// Biomass at which a seedling counts as mature.
+const MATURE_BIOMASS: f32 = 4.0;
+/// Plants with less biomass than this have nothing worth eating.
+const MIN_EDIBLE_BIOMASS: f32 = 1.0;
+/// Ticks a plant has to stay fully grown before it starts seeding.
+const TICKS_TO_SEED: u32 = 30;
+/// Chance per tick that a seeding plant drops a seed.
+const SEED_CHANCE: f32 = 0.05;
+/// Furthest a seed lands from its parent, in tiles.
+const SEED_RADIUS: i32 = 2;
+/// Biomass of a plant that has just sprouted from seed.
+const SEEDLING_BIOMASS: f32 = 0.5;
+/// A plant grazed down to nothing more often than this before seeding again dies.
+const MAX_TIMES_GRAZED_BARE: u32 = 3;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GrowthStage {
+    Seedling,
+    Mature,
+    Seeding,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PlantSpecies {
+    Grass,
+    Clover,
+    BerryBush,
+    ToxicWeed,
+}
+
+impl PlantSpecies {
+    pub const ALL: [PlantSpecies; 4] = [PlantSpecies::Grass, PlantSpecies::Clover, PlantSpecies::BerryBush, PlantSpecies::ToxicWeed];
+
+    /// Biomass grown per tick in average conditions.
+    pub fn growth_rate(&self) -> f32 {
+        match self {
+            PlantSpecies::Grass => 0.25,
+            PlantSpecies::Clover => 0.2,
+            PlantSpecies::BerryBush => 0.08,
+            PlantSpecies::ToxicWeed => 0.15,
+        }
+    }
+
+    /// Hunger restored per unit of biomass eaten.
+    pub fn nutrition(&self) -> f32 {
+        match self {
+            PlantSpecies::Grass => 10.0 / 3.0,
+            PlantSpecies::Clover => 4.0,
+            PlantSpecies::BerryBush => 5.0,
+            PlantSpecies::ToxicWeed => 1.0,
+        }
+    }
+
+    /// Thirst quenched per unit of biomass eaten, for juicy plants.
+    pub fn thirst_contribution(&self) -> f32 {
+        match self {
+            PlantSpecies::Grass => 0.0,
+            PlantSpecies::Clover => 0.5,
+            PlantSpecies::BerryBush => 2.0,
+            PlantSpecies::ToxicWeed => 0.0,
+        }
+    }
+
+    /// Harm done per unit of biomass eaten.
+    pub fn toxicity(&self) -> f32 {
+        match self {
+            PlantSpecies::ToxicWeed => 4.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Ground the species grows best on. Elsewhere it grows and germinates at half the rate.
+    pub fn preferred_biome(&self) -> VoxelType {
+        match self {
+            PlantSpecies::ToxicWeed => VoxelType::SandVoxel,
+            PlantSpecies::BerryBush => VoxelType::ForestVoxel,
+            _ => VoxelType::GrassVoxel,
+        }
+    }
+
+    /// How much rabbits favour this species; a plant twice as preferred is worth walking twice as far for.
+    pub fn rabbit_preference(&self) -> f32 {
+        match self {
+            PlantSpecies::Grass => 1.0,
+            PlantSpecies::Clover => 1.5,
+            PlantSpecies::BerryBush => 2.0,
+            PlantSpecies::ToxicWeed => 0.2,
+        }
+    }
+
+    /// Half extents of the fully grown plant.
+    fn size(&self) -> Vec3 {
+        match self {
+            PlantSpecies::Grass => Vec3::new(0.1, 0.3, 0.1),
+            PlantSpecies::Clover => Vec3::new(0.2, 0.12, 0.2),
+            PlantSpecies::BerryBush => Vec3::new(0.3, 0.35, 0.3),
+            PlantSpecies::ToxicWeed => Vec3::new(0.08, 0.35, 0.08),
+        }
+    }
+
+    fn colour(&self) -> Color {
+        match self {
+            PlantSpecies::Grass => Color::linear_rgb(0.1, 0.9, 0.4),
+            PlantSpecies::Clover => Color::linear_rgb(0.2, 0.6, 0.1),
+            PlantSpecies::BerryBush => Color::linear_rgb(0.5, 0.1, 0.4),
+            PlantSpecies::ToxicWeed => Color::linear_rgb(0.8, 0.8, 0.1),
+        }
+    }
+
+    /// Picks a species for a plant sprouting on the given ground, if anything grows there at all.
+    pub fn random_for(voxel_type: VoxelType) -> Option<PlantSpecies> {
+        let roll = rand::random::<f32>();
+        match voxel_type {
+            VoxelType::WaterVoxel => None,
+            VoxelType::SandVoxel => Some(PlantSpecies::ToxicWeed),
+            VoxelType::ForestVoxel => Some(match roll {
+                r if r < 0.3 => PlantSpecies::Grass,
+                r if r < 0.5 => PlantSpecies::Clover,
+                r if r < 0.95 => PlantSpecies::BerryBush,
+                _ => PlantSpecies::ToxicWeed,
+            }),
+            VoxelType::GrassVoxel => Some(match roll {
+                r if r < 0.6 => PlantSpecies::Grass,
+                r if r < 0.8 => PlantSpecies::Clover,
+                r if r < 0.9 => PlantSpecies::BerryBush,
+                _ => PlantSpecies::ToxicWeed,
+            }),
+        }
+    }
+}
 
 #[derive(Component, Clone)]
 pub struct Foliage {
     pub location: (i32, i32),
-    pub consumed: bool,
-    pub regen_counter: u32,
+    pub species: PlantSpecies,
+    pub stage: GrowthStage,
+    /// Edible plant matter, up to `MAX_BIOMASS`.
+    pub biomass: f32,
+    /// Ticks the plant has spent fully grown.
+    pub ticks_at_full: u32,
+    /// Times the plant has been grazed down to nothing since it last set seed.
+    pub times_grazed_bare: u32,
 }
 
+impl Foliage {
+    pub fn new(location: (i32, i32), species: PlantSpecies, biomass: f32) -> Self {
+        let mut foliage = Foliage {
+            location,
+            species,
+            stage: GrowthStage::Seedling,
+            biomass,
+            ticks_at_full: 0,
+            times_grazed_bare: 0,
+        };
+        foliage.update_stage();
+        foliage
+    }
+
+    pub fn is_edible(&self) -> bool {
+        self.biomass >= MIN_EDIBLE_BIOMASS
+    }
+
+    /// Biomass a bite would take from this plant: `bite_size` from a fully grown plant, and
+    /// proportionally less the sparser the plant has been grazed or the younger it is.
+    pub fn bite(&self, bite_size: f32) -> f32 {
+        (bite_size * self.biomass / MAX_BIOMASS).min(self.biomass)
+    }
+
+    fn update_stage(&mut self) {
+        self.stage = if self.ticks_at_full >= TICKS_TO_SEED {
+            GrowthStage::Seeding
+        } else if self.biomass >= MATURE_BIOMASS {
+            GrowthStage::Mature
+        } else {
+            GrowthStage::Seedling
+        };
+    }
+}
+
+/// A plant was grazed, losing the given amount of biomass.
 #[derive(Event)]
-pub struct FoliageConsumedEvent(pub Entity);
+pub struct FoliageConsumedEvent(pub Entity, pub f32);
+
+/// Meshes and materials shared by every plant of a species so they can be drawn as instances.
+/// Both are indexed by `PlantSpecies as usize`.
+#[derive(Resource)]
+pub struct FoliageAssets {
+    pub meshes: Vec<Handle<Mesh>>,
+    pub materials: Vec<Handle<StandardMaterial>>,
+}
 
 pub(super) fn plugin(app: &mut App) {
     app
         .add_event::<FoliageConsumedEvent>()
-        .add_systems(Update, (initial_foliage_spawn, consume_foliage, regenerate_foliage));
+        .add_systems(Startup, setup_foliage_assets)
+        .add_systems(Update, (initial_foliage_spawn, consume_foliage, (grow_foliage, disperse_seeds).chain().after(crate::world_setup::update_active_chunks), update_foliage_visuals));
 }
 
-fn initial_foliage_spawn(
+fn setup_foliage_assets(
     mut commands: Commands,
-    mut events: EventReader<VoxelsSpawnedEvent>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    mut voxel_query: Query<(Entity, &Transform, &Voxel), With<Voxel>>,
+) {
+    // The mesh origin sits at the base of the plant so scaling grows it out of the ground.
+    let foliage_meshes = PlantSpecies::ALL.iter().map(|species| {
+        let half_size = species.size();
+        meshes.add(Mesh::from(Cuboid { half_size }).translated_by(Vec3::new(0.0, half_size.y, 0.0)))
+    }).collect();
+    let foliage_materials = PlantSpecies::ALL.iter().map(|species| {
+        materials.add(StandardMaterial {
+            base_color: species.colour(),
+            ..default()
+        })
+    }).collect();
+
+    commands.insert_resource(FoliageAssets {
+        meshes: foliage_meshes,
+        materials: foliage_materials,
+    });
+}
+
+fn initial_foliage_spawn(
+    mut commands: Commands,
+    mut events: EventReader<WorldMapDataSetEvent>,
+    foliage_assets: Res<FoliageAssets>,
+    season_cycle: Res<SeasonCycle>,
+    mut world_map_query: Query<&mut WorldMap>,
 ) {
 
     for active_event in events.read() {
         match active_event {
-            VoxelsSpawnedEvent(_world_voxels) => {
+            WorldMapDataSetEvent => {
                 
                 println!("Event received!");
 
-                let foliage = Mesh3d(meshes.add(Cuboid {
-                    half_size: Vec3::new(0.1, 0.3, 0.1),
-                    ..default()
-                }));
-
-                let foliage_mat = MeshMaterial3d(materials.add(StandardMaterial {
-                    base_color: Color::linear_rgb(0.1, 0.9,0.4),
-                    ..default()
-                }));
+                let mut world_map = world_map_query.single_mut();
+                let spawn_chance = 0.1 * season_cycle.season.foliage_growth();
 
-                for (entity, transform, voxel) in voxel_query.iter_mut() {
-                    if voxel.voxel_type != VoxelType::GrassVoxel {
-                        continue;
-                    }
-                    if rand::random::<f32>() < 0.1 {
-                        spawn_single_foliage(entity, &transform.translation, &mut commands, foliage.clone(), foliage_mat.clone());
+                for x in 0..world_map.width {
+                    for z in 0..world_map.height {
+                        let Some(voxel_type) = world_map.voxel_type((x, z)) else {
+                            continue;
+                        };
+                        // Sand is sparsely covered compared to grass.
+                        let germination = voxel_type.seed_germination() / VoxelType::GrassVoxel.seed_germination();
+                        if rand::random::<f32>() >= spawn_chance * germination {
+                            continue;
+                        }
+                        if let Some(species) = PlantSpecies::random_for(voxel_type) {
+                            let biomass = MATURE_BIOMASS + rand::random::<f32>() * (MAX_BIOMASS - MATURE_BIOMASS);
+                            spawn_single_foliage((x, z), species, biomass, &mut world_map, &mut commands, &foliage_assets);
+                        }
                     }
                 }
             }
@@ -59,59 +268,165 @@ fn initial_foliage_spawn(
     }
 }
 
-fn spawn_single_foliage(
-    entity: Entity,
-    position: &Vec3,
+pub fn spawn_single_foliage(
+    location: (i32, i32),
+    species: PlantSpecies,
+    biomass: f32,
+    world_map: &mut WorldMap,
     commands: &mut Commands,
-    foliage: Mesh3d,
-    foliage_mat: MeshMaterial3d<StandardMaterial>,
+    foliage_assets: &FoliageAssets,
 ) {
-    commands.entity(entity).with_children(|parent| {
-        parent.spawn((
-            Foliage {
-                location: ((position.x as i32) + 30, (position.z as i32) + 30),
-                consumed: false,
-                regen_counter: 0,
-            },
-            foliage,
-            foliage_mat,
-            Visibility::Visible,
-            Transform::from_translation(Vec3::new(0.0, 0.75, 0.0)),
-        ));
-    });
+    let surface_height = world_map.voxel_type(location).unwrap_or(VoxelType::GrassVoxel).surface_height();
+    let foliage_entity = commands.spawn((
+        Foliage::new(location, species, biomass),
+        Mesh3d(foliage_assets.meshes[species as usize].clone()),
+        MeshMaterial3d(foliage_assets.materials[species as usize].clone()),
+        Visibility::Visible,
+        Transform::from_translation(world_map.to_world(location, surface_height)),
+    )).id();
+    world_map.set_foliage(location, Some(foliage_entity));
 }
 
 
 fn consume_foliage(
-    mut foliage_query: Query<(&mut Foliage, &mut Visibility)>,
+    mut commands: Commands,
+    mut foliage_query: Query<&mut Foliage>,
+    mut world_map_query: Query<&mut WorldMap>,
     mut consumed_foliage_event: EventReader<FoliageConsumedEvent>,
 ) {
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
     for active_event in consumed_foliage_event.read() {
         match active_event {
-            FoliageConsumedEvent(entity) => {
-                let Ok((mut foliage, mut visibility)) = foliage_query.get_mut(*entity) else {
+            FoliageConsumedEvent(entity, eaten) => {
+                let Ok(mut foliage) = foliage_query.get_mut(*entity) else {
                     continue;
                 };
-                foliage.consumed = true;
-                *visibility = Visibility::Hidden;
+                foliage.biomass = (foliage.biomass - eaten).max(0.0);
+                foliage.ticks_at_full = 0;
+
+                if !foliage.is_edible() {
+                    foliage.times_grazed_bare += 1;
+                    if foliage.times_grazed_bare > MAX_TIMES_GRAZED_BARE {
+                        // Grazed to the roots too often to recover.
+                        world_map.set_foliage(foliage.location, None);
+                        commands.entity(*entity).despawn();
+                        continue;
+                    }
+                }
+                foliage.update_stage();
             }
         }
     }
 }
 
-fn regenerate_foliage(
+fn grow_foliage(
     frame_control: Res<FrameControl>,
-    mut foliage_query: Query<(&mut Foliage, &mut Visibility)>,
+    season_cycle: Res<SeasonCycle>,
+    world_map_query: Query<&WorldMap>,
+    mut foliage_query: Query<&mut Foliage>,
 ) {
     if frame_control.timer.finished() {
-        for (mut foliage, mut visibility) in foliage_query.iter_mut() {
-            if foliage.consumed && foliage.regen_counter > 50 {
-                foliage.consumed = false;
-                *visibility = Visibility::Visible;
-                foliage.regen_counter = 0;
-            } else if foliage.consumed {
-                foliage.regen_counter += 1;
+        let Ok(world_map) = world_map_query.get_single() else {
+            return;
+        };
+
+        let growth = season_cycle.season.foliage_growth();
+
+        for mut foliage in foliage_query.iter_mut() {
+            // Dormant chunks are caught up in one step when they wake.
+            let Some(ticks) = world_map.simulated_ticks(foliage.location) else {
+                continue;
+            };
+
+            if foliage.biomass >= MAX_BIOMASS {
+                foliage.ticks_at_full += ticks;
+                if foliage.ticks_at_full >= TICKS_TO_SEED {
+                    foliage.times_grazed_bare = 0;
+                }
+            } else {
+                // Moist soil regrows up to three times faster than parched soil, and
+                // overgrazed soil regrows slower than soil at its natural fertility.
+                let moisture = world_map.moisture(foliage.location);
+                let fertility = world_map.fertility(foliage.location) / VoxelType::GrassVoxel.base_fertility();
+                let biome = if world_map.voxel_type(foliage.location) == Some(foliage.species.preferred_biome()) { 1.0 } else { 0.5 };
+                let rate = foliage.species.growth_rate() * growth * (0.5 + moisture) * fertility * biome;
+                foliage.biomass = (foliage.biomass + ticks as f32 * rate).min(MAX_BIOMASS);
             }
+            foliage.update_stage();
         }
     }
-}
\ No newline at end of file
+}
+
+/// Seeding plants scatter seeds onto empty tiles around them. Whether a seed takes root
+/// depends on the biome it lands on and how fertile the soil there is.
+fn disperse_seeds(
+    mut commands: Commands,
+    frame_control: Res<FrameControl>,
+    foliage_assets: Res<FoliageAssets>,
+    mut world_map_query: Query<&mut WorldMap>,
+    mut foliage_query: Query<&mut Foliage>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+    let mut seeds = Vec::new();
+
+    for mut foliage in foliage_query.iter_mut() {
+        if foliage.stage != GrowthStage::Seeding {
+            continue;
+        }
+        let Some(ticks) = world_map.simulated_ticks(foliage.location) else {
+            continue;
+        };
+        if rng.gen::<f32>() >= SEED_CHANCE * ticks as f32 {
+            continue;
+        }
+
+        let target = (
+            foliage.location.0 + rng.gen_range(-SEED_RADIUS..=SEED_RADIUS),
+            foliage.location.1 + rng.gen_range(-SEED_RADIUS..=SEED_RADIUS),
+        );
+        seeds.push((target, foliage.species));
+
+        // Setting seed costs the plant its stored growth, so it has to mature again before the next round.
+        foliage.ticks_at_full = 0;
+        foliage.update_stage();
+    }
+
+    for (location, species) in seeds {
+        let Some(voxel_type) = world_map.voxel_type(location) else {
+            continue;
+        };
+        if world_map.foliage_at(location).is_some() {
+            continue;
+        }
+        let biome = if voxel_type == species.preferred_biome() { 1.0 } else { 0.5 };
+        let germination = voxel_type.seed_germination() * biome * world_map.fertility(location) / VoxelType::GrassVoxel.base_fertility();
+        if rng.gen::<f32>() < germination {
+            spawn_single_foliage(location, species, SEEDLING_BIOMASS, &mut world_map, &mut commands, &foliage_assets);
+        }
+    }
+}
+
+/// Scales each plant with its biomass and hides plants that have been grazed bare.
+fn update_foliage_visuals(
+    mut foliage_query: Query<(&Foliage, &mut Transform, &mut Visibility), Changed<Foliage>>,
+) {
+    for (foliage, mut transform, mut visibility) in foliage_query.iter_mut() {
+        let growth = foliage.biomass / MAX_BIOMASS;
+        transform.scale = Vec3::new(0.5 + 0.5 * growth, growth.max(0.05), 0.5 + 0.5 * growth);
+        *visibility = if foliage.is_edible() {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    carcass::Carcass,
+    fish::{Fish, FISH_MEAT},
+    frame_manager::FrameControl,
+    health::{InjuryEvent, MAX_HEALTH},
+    movement::MovementTween,
+    rabbit::Rabbit,
+    scenario::Scenario,
+    social::Predator,
+    world_setup::{VoxelType, WorldMap, WorldMapDataSetEvent},
+};
+
+/// Hawk settings, set from the scenario's `hawks` section.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct HawkConfig {
+    /// Hawks released once the world has been built.
+    pub count: u32,
+    /// Tiles a hawk can see from the air in every direction.
+    pub sight: i32,
+    /// Tiles flown per tick while soaring.
+    pub soar_speed: i32,
+    /// Tiles covered per tick while diving on prey.
+    pub dive_speed: i32,
+    /// Chance that a dive that reaches its prey catches it.
+    pub catch_chance: f32,
+    /// Hunger lost per tick.
+    pub hunger_decay: f32,
+    /// Below this hunger a hawk starts hunting.
+    pub hunt_threshold: f32,
+}
+
+impl Default for HawkConfig {
+    fn default() -> Self {
+        HawkConfig {
+            count: 2,
+            sight: 10,
+            soar_speed: 1,
+            dive_speed: 3,
+            catch_chance: 0.5,
+            hunger_decay: 0.2,
+            hunt_threshold: 60.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HawkState {
+    /// Circling towards a waypoint, watching the ground for prey.
+    Soaring((i32, i32)),
+    /// Stooping on a rabbit, or a fish in the shallows.
+    Diving(Entity),
+    /// On the ground eating the carcass at a location.
+    Feeding((i32, i32)),
+}
+
+/// A bird of prey. Hawks fly over any terrain, water included, and only see rabbits out in the open
+/// and fish close enough to the shore to snatch from the water.
+#[derive(Component)]
+pub struct Hawk {
+    pub location: (i32, i32),
+    pub state: HawkState,
+    /// From 0.0 (starving) to `MAX_HAWK_HUNGER`. Hawks that are not hungry ignore prey.
+    pub hunger: f32,
+}
+
+#[derive(Resource)]
+pub struct HawkAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+const MAX_HAWK_HUNGER: f32 = 100.0;
+/// Height hawks soar at above the ground.
+const FLIGHT_HEIGHT: f32 = 4.0;
+/// Height of a hawk standing on the ground.
+const LANDED_HEIGHT: f32 = 0.05;
+/// Meat a feeding hawk tears from a carcass each tick.
+const MEAT_PER_TICK: f32 = 1.0;
+/// Hunger restored per unit of meat eaten.
+const MEAT_NUTRITION: f32 = 10.0;
+
+pub(super) fn plugin(app: &mut App) {
+    let hawk_config = app.world().resource::<Scenario>().hawks.clone();
+    app
+        .insert_resource(hawk_config)
+        .add_systems(Startup, setup_hawk_assets)
+        .add_systems(Update, (spawn_initial_hawks, hawk_behaviour));
+}
+
+fn setup_hawk_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(HawkAssets {
+        // Wide wings across a short body, pointing along +z like the rabbits.
+        mesh: meshes.add(Cuboid::new(0.9, 0.08, 0.35)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(0.3, 0.18, 0.08),
+            ..default()
+        }),
+    });
+}
+
+pub fn spawn_hawk(
+    location: (i32, i32),
+    world_map: &WorldMap,
+    commands: &mut Commands,
+    hawk_assets: &HawkAssets,
+) -> Entity {
+    let position = world_map.to_world(location, FLIGHT_HEIGHT);
+    commands.spawn((
+        Hawk {
+            location,
+            state: HawkState::Soaring(location),
+            hunger: MAX_HAWK_HUNGER,
+        },
+        Predator,
+        Mesh3d(hawk_assets.mesh.clone()),
+        MeshMaterial3d(hawk_assets.material.clone()),
+        Transform::from_translation(position),
+        MovementTween::new(position),
+    )).id()
+}
+
+fn spawn_initial_hawks(
+    mut commands: Commands,
+    mut events: EventReader<WorldMapDataSetEvent>,
+    hawk_config: Res<HawkConfig>,
+    hawk_assets: Res<HawkAssets>,
+    world_map_query: Query<&WorldMap>,
+) {
+    for _ in events.read() {
+        let Ok(world_map) = world_map_query.get_single() else {
+            continue;
+        };
+        for _ in 0..hawk_config.count {
+            spawn_hawk(random_location(world_map), world_map, &mut commands, &hawk_assets);
+        }
+    }
+}
+
+fn random_location(world_map: &WorldMap) -> (i32, i32) {
+    let mut rng = rand::thread_rng();
+    (rng.gen_range(0..world_map.width), rng.gen_range(0..world_map.height))
+}
+
+/// Whether a hawk overhead can see the rabbit: not down a burrow and not under the trees.
+fn visible_from_air(rabbit: &Rabbit, world_map: &WorldMap) -> bool {
+    !rabbit.in_burrow && !world_map.voxel_type(rabbit.location).is_some_and(|voxel_type| voxel_type.gives_cover())
+}
+
+/// Whether a fish is in shallow water next to the shore, where a hawk can take it.
+fn in_shallows(location: (i32, i32), world_map: &WorldMap) -> bool {
+    world_map.tiles_within(location, 1).any(|tile| !world_map.is_swimmable(tile))
+}
+
+/// Flies straight towards `target` for up to `tiles` tiles, whatever the ground below.
+fn fly_towards(location: (i32, i32), target: (i32, i32), tiles: i32) -> (i32, i32) {
+    let (dx, dz) = (target.0 - location.0, target.1 - location.1);
+    (location.0 + dx.clamp(-tiles, tiles), location.1 + dz.clamp(-tiles, tiles))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn hawk_behaviour(
+    frame_control: Res<FrameControl>,
+    hawk_config: Res<HawkConfig>,
+    world_map_query: Query<&WorldMap>,
+    mut hawk_query: Query<(&mut Hawk, &mut MovementTween)>,
+    rabbit_query: Query<(Entity, &Rabbit)>,
+    mut fish_query: Query<(Entity, &mut Fish)>,
+    mut carcass_query: Query<&mut Carcass>,
+    mut injury_event_writer: EventWriter<InjuryEvent>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+
+    // Where a hawk can see each prey animal, if it can see it at all.
+    let prey_location = |prey_entity: Entity| -> Option<(i32, i32)> {
+        if let Ok((_, rabbit)) = rabbit_query.get(prey_entity) {
+            return visible_from_air(rabbit, world_map).then_some(rabbit.location);
+        }
+        fish_query.get(prey_entity).ok()
+            .filter(|(_, fish)| !fish.caught)
+            .map(|(_, fish)| fish.location)
+            .filter(|&location| in_shallows(location, world_map))
+    };
+
+    let mut rng = rand::thread_rng();
+    let mut caught_fish: Vec<Entity> = Vec::new();
+    for (mut hawk, mut tween) in hawk_query.iter_mut() {
+        hawk.hunger = (hawk.hunger - hawk_config.hunger_decay).max(0.0);
+        let hungry = hawk.hunger < hawk_config.hunt_threshold;
+        let previous_location = hawk.location;
+        let mut height = FLIGHT_HEIGHT;
+
+        hawk.state = match hawk.state {
+            HawkState::Soaring(waypoint) => {
+                let in_sight = |location: (i32, i32)| {
+                    (location.0 - hawk.location.0).abs() <= hawk_config.sight && (location.1 - hawk.location.1).abs() <= hawk_config.sight
+                };
+                let prey = rabbit_query.iter().map(|(entity, _)| entity)
+                    .chain(fish_query.iter().map(|(entity, _)| entity))
+                    .filter(|_| hungry)
+                    .filter_map(|prey_entity| prey_location(prey_entity).map(|location| (prey_entity, location)))
+                    .filter(|&(_, location)| in_sight(location))
+                    .min_by_key(|(_, location)| (location.0 - hawk.location.0).abs().max((location.1 - hawk.location.1).abs()))
+                    .map(|(prey_entity, _)| prey_entity);
+                let carrion = carcass_query.iter()
+                    .filter(|carcass| hungry && carcass.meat > 0.0 && in_sight(carcass.location))
+                    .map(|carcass| carcass.location)
+                    .next();
+
+                if let Some(prey_entity) = prey {
+                    HawkState::Diving(prey_entity)
+                } else if let Some(carcass_location) = carrion {
+                    // Nothing alive in the open, so settle for carrion.
+                    hawk.location = fly_towards(hawk.location, carcass_location, hawk_config.soar_speed);
+                    if hawk.location == carcass_location { HawkState::Feeding(carcass_location) } else { HawkState::Soaring(waypoint) }
+                } else if hawk.location == waypoint {
+                    HawkState::Soaring(random_location(world_map))
+                } else {
+                    hawk.location = fly_towards(hawk.location, waypoint, hawk_config.soar_speed);
+                    HawkState::Soaring(waypoint)
+                }
+            }
+            HawkState::Diving(prey_entity) => {
+                // The dive is broken off if a rabbit dives into its burrow or under the trees, or a fish swims out into deep water.
+                match prey_location(prey_entity).filter(|_| !caught_fish.contains(&prey_entity)) {
+                    Some(location) => {
+                        hawk.location = fly_towards(hawk.location, location, hawk_config.dive_speed);
+                        if hawk.location != location {
+                            height = FLIGHT_HEIGHT / 2.0;
+                            HawkState::Diving(prey_entity)
+                        } else if rng.gen::<f32>() >= hawk_config.catch_chance {
+                            HawkState::Soaring(random_location(world_map))
+                        } else if rabbit_query.contains(prey_entity) {
+                            // A rabbit is killed and eaten where it fell.
+                            injury_event_writer.send(InjuryEvent(prey_entity, MAX_HEALTH));
+                            height = LANDED_HEIGHT;
+                            HawkState::Feeding(location)
+                        } else {
+                            // A fish is carried off and eaten whole.
+                            injury_event_writer.send(InjuryEvent(prey_entity, MAX_HEALTH));
+                            caught_fish.push(prey_entity);
+                            hawk.hunger = (hawk.hunger + FISH_MEAT * MEAT_NUTRITION).min(MAX_HAWK_HUNGER);
+                            HawkState::Soaring(random_location(world_map))
+                        }
+                    }
+                    None => HawkState::Soaring(random_location(world_map)),
+                }
+            }
+            HawkState::Feeding(location) => {
+                let carcass = carcass_query.iter_mut().find(|carcass| carcass.location == location && carcass.meat > 0.0);
+                match carcass {
+                    Some(mut carcass) if hawk.hunger < MAX_HAWK_HUNGER => {
+                        let eaten = carcass.scavenge(MEAT_PER_TICK);
+                        hawk.hunger = (hawk.hunger + eaten * MEAT_NUTRITION).min(MAX_HAWK_HUNGER);
+                        height = LANDED_HEIGHT;
+                        HawkState::Feeding(location)
+                    }
+                    _ => HawkState::Soaring(random_location(world_map)),
+                }
+            }
+        };
+
+        let ground = world_map.voxel_type(hawk.location).unwrap_or(VoxelType::GrassVoxel).surface_height();
+        let position = world_map.to_world(hawk.location, ground + height);
+        if hawk.location != previous_location || tween.to != position {
+            tween.move_to(position, 0);
+        }
+    }
+
+    for fish_entity in caught_fish {
+        if let Ok((_, mut fish)) = fish_query.get_mut(fish_entity) {
+            fish.caught = true;
+        }
+    }
+}
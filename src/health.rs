@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::{
+    carcass::SpawnCarcassEvent,
+    disease::DiseaseState,
+    frame_manager::FrameControl,
+    rabbit::{Rabbit, RabbitResource},
+};
+
+/// An animal's health. Depleted needs, injuries and disease wear it down, and the animal dies when it reaches zero.
+#[derive(Component, Clone, Debug)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Health {
+            current: MAX_HEALTH,
+            max: MAX_HEALTH,
+        }
+    }
+}
+
+impl Health {
+    pub fn damage(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+    }
+
+    pub fn heal(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    /// Takes away all remaining health, e.g. for death of old age.
+    pub fn kill(&mut self) {
+        self.current = 0.0;
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+}
+
+/// Harm done to an animal from outside: a wound, a fall or a poisonous meal.
+#[derive(Event)]
+pub struct InjuryEvent(pub Entity, pub f32);
+
+pub const MAX_HEALTH: f32 = 100.0;
+/// Health lost each tick for every need, hunger or thirst, that has run out.
+const DEPLETED_NEED_DAMAGE: f32 = 2.0;
+/// Health regained each tick while the animal is well fed, watered and free of disease.
+const HEALTH_REGEN: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app
+        .add_event::<InjuryEvent>()
+        .add_systems(Update, (apply_injuries, apply_rabbit_need_damage, remove_dead_rabbits).chain());
+}
+
+fn apply_injuries(
+    mut events: EventReader<InjuryEvent>,
+    mut health_query: Query<&mut Health>,
+) {
+    for InjuryEvent(entity, amount) in events.read() {
+        let Ok(mut health) = health_query.get_mut(*entity) else {
+            continue;
+        };
+        health.damage(*amount);
+    }
+}
+
+/// Starving and parched rabbits waste away, while those with their needs met recover.
+fn apply_rabbit_need_damage(
+    frame_control: Res<FrameControl>,
+    mut rabbit_query: Query<(&Rabbit, &mut Health)>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    for (rabbit, mut health) in rabbit_query.iter_mut() {
+        let depleted_needs = (rabbit.hunger <= 0.0) as u32 + (rabbit.thirst <= 0.0) as u32;
+        if depleted_needs > 0 {
+            health.damage(DEPLETED_NEED_DAMAGE * depleted_needs as f32);
+        } else if rabbit.hunger >= rabbit.satisfaction_threshold
+            && rabbit.thirst >= rabbit.satisfaction_threshold
+            && !matches!(rabbit.disease, DiseaseState::Infected(_)) {
+            health.heal(HEALTH_REGEN);
+        }
+    }
+}
+
+/// The one place rabbits die, whatever killed them, leaving a carcass behind.
+fn remove_dead_rabbits(
+    mut commands: Commands,
+    mut rabbit_resource: ResMut<RabbitResource>,
+    rabbit_query: Query<(Entity, &Rabbit, &Health), Changed<Health>>,
+    mut carcass_event_writer: EventWriter<SpawnCarcassEvent>,
+) {
+    for (rabbit_entity, rabbit, health) in rabbit_query.iter() {
+        if !health.is_dead() {
+            continue;
+        }
+        rabbit_resource.rabbits.retain(|&x| x != rabbit_entity);
+        commands.entity(rabbit_entity).despawn();
+        carcass_event_writer.send(SpawnCarcassEvent(rabbit.location, rabbit.carcass_meat()));
+    }
+}
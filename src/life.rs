@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    movement::MovementTween,
+    rabbit::Rabbit,
+    scenario::Scenario,
+    world_setup::WorldMap,
+};
+
+/// Stage of an animal's life, from helpless newborn to old age.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LifeStage {
+    /// Too small to fend for itself; follows its mother and suckles.
+    Kit,
+    /// Weaned and still growing, quick on its feet.
+    Juvenile,
+    Adult,
+    /// Slower and shorter sighted.
+    Elder,
+}
+
+impl LifeStage {
+    /// Multiplier on the speed gene.
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            LifeStage::Kit => 0.6,
+            LifeStage::Juvenile => 1.1,
+            LifeStage::Adult => 1.0,
+            LifeStage::Elder => 0.7,
+        }
+    }
+
+    /// Tiles added to, or taken from, the sight distance gene.
+    pub fn sight_modifier(&self) -> i32 {
+        match self {
+            LifeStage::Kit | LifeStage::Elder => -1,
+            LifeStage::Juvenile | LifeStage::Adult => 0,
+        }
+    }
+
+    pub fn can_breed(&self) -> bool {
+        matches!(self, LifeStage::Adult | LifeStage::Elder)
+    }
+}
+
+/// Ages, in age ticks, at which an animal moves to the next life stage.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct LifeConfig {
+    /// Age at which a kit is weaned and becomes a juvenile.
+    pub juvenile_age: u32,
+    /// Age at which an animal is fully grown and can breed.
+    pub adult_age: u32,
+    /// Age from which an animal slows down and its sight fades.
+    pub elder_age: u32,
+    /// Fraction of its adult size an animal is born at.
+    pub birth_size: f32,
+}
+
+impl Default for LifeConfig {
+    fn default() -> Self {
+        LifeConfig {
+            juvenile_age: 8,
+            adult_age: 20,
+            elder_age: 50,
+            birth_size: 0.4,
+        }
+    }
+}
+
+impl LifeConfig {
+    pub fn stage(&self, age: u32) -> LifeStage {
+        if age < self.juvenile_age {
+            LifeStage::Kit
+        } else if age < self.adult_age {
+            LifeStage::Juvenile
+        } else if age < self.elder_age {
+            LifeStage::Adult
+        } else {
+            LifeStage::Elder
+        }
+    }
+
+    /// Fraction of adult size reached at an age, growing steadily from `birth_size` until adulthood.
+    pub fn growth(&self, age: u32) -> f32 {
+        if age >= self.adult_age {
+            return 1.0;
+        }
+        let progress = age as f32 / self.adult_age as f32;
+        self.birth_size + (1.0 - self.birth_size) * progress
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let life_config = app.world().resource::<Scenario>().rabbit_life.clone();
+    app
+        .insert_resource(life_config)
+        .add_systems(Update, update_rabbit_growth);
+}
+
+/// Scales growing rabbits up, and keeps resting ones standing on the ground as they grow.
+fn update_rabbit_growth(
+    world_map_query: Query<&WorldMap>,
+    mut rabbit_query: Query<(&Rabbit, &MovementTween, &mut Transform), Changed<Rabbit>>,
+) {
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+    for (rabbit, tween, mut transform) in rabbit_query.iter_mut() {
+        transform.scale = rabbit.size() * 2.0;
+        if tween.progress >= 1.0 {
+            transform.translation = rabbit.world_position(world_map);
+        }
+    }
+}
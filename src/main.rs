@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use crate::day_night::TimeOfDay;
+use crate::map_image::MapImageFormat;
 use crate::rabbit::Rabbit;
+use crate::seasons::SeasonCycle;
+use crate::weather::Weather;
+use crate::world_setup::ExportWorldMapEvent;
 
 
 mod world_setup;
@@ -9,6 +14,26 @@ mod camera_setup;
 mod rabbit;
 mod frame_manager;
 mod foliage;
+mod day_night;
+mod seasons;
+mod weather;
+mod soil;
+mod carcass;
+mod burrow;
+mod movement;
+mod memory;
+mod social;
+mod disease;
+mod stats;
+mod life;
+mod mortality;
+mod health;
+mod needs;
+mod hawk;
+mod fish;
+mod editor;
+mod map_image;
+mod scenario;
 
 fn main() {
     
@@ -16,7 +41,8 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_plugins(EguiPlugin)
         .add_plugins(FrameTimeDiagnosticsPlugin::default())
-        .add_plugins((world_setup::plugin, camera_setup::plugin, rabbit::plugin, frame_manager::plugin, foliage::plugin))
+        .add_plugins((scenario::plugin, world_setup::plugin, camera_setup::plugin, rabbit::plugin, frame_manager::plugin, foliage::plugin, editor::plugin, day_night::plugin, seasons::plugin, weather::plugin, soil::plugin, carcass::plugin, burrow::plugin, movement::plugin, social::plugin))
+        .add_plugins((disease::plugin, stats::plugin, life::plugin, mortality::plugin, health::plugin, needs::plugin, hawk::plugin, fish::plugin))
         .add_systems(Update, ui_example_system)
         .run();
 
@@ -25,8 +51,23 @@ fn main() {
 fn ui_example_system(
     mut contexts: EguiContexts,
     entity_query: Query<(Entity, &Rabbit), With<Rabbit>>,
+    mut export_event_writer: EventWriter<ExportWorldMapEvent>,
+    time_of_day: Res<TimeOfDay>,
+    season_cycle: Res<SeasonCycle>,
+    weather: Res<Weather>,
 ) {
     egui::Window::new("Hello").show(contexts.ctx_mut(), |ui| {
+        ui.label(format!("Year {}, {:?}", season_cycle.year, season_cycle.season));
+        ui.label(format!("Day {}, {:?}", time_of_day.day, time_of_day.phase()));
+        ui.label(format!("Weather: {:?}, {} puddles, {} dried tiles", weather.state, weather.puddles.len(), weather.dried.len()));
+        ui.horizontal(|ui| {
+            if ui.button("Export heightmap").clicked() {
+                export_event_writer.send(ExportWorldMapEvent("heightmap.png".to_string(), MapImageFormat::Heightmap));
+            }
+            if ui.button("Export colour map").clicked() {
+                export_event_writer.send(ExportWorldMapEvent("colour_map.png".to_string(), MapImageFormat::ColourMap));
+            }
+        });
         entity_query.iter().for_each(|(entity, rabbit)| {
             ui.label(format!("{:?}, {:?}", entity, rabbit.location));
             for partner_entity in rabbit.partner_in_range.iter() {
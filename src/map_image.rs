@@ -0,0 +1,98 @@
+use image::{GrayImage, Luma, Rgb, RgbImage};
+use serde::Deserialize;
+
+use crate::world_setup::{VoxelType, WorldMap};
+
+/// Greyscale heights below this are water, matching the Perlin generator's -0.15 cut-off.
+const HEIGHTMAP_WATER_LEVEL: f32 = 0.425;
+/// Greyscale heights below this (and above the water level) are sand.
+const HEIGHTMAP_SAND_LEVEL: f32 = 0.525;
+/// Greyscale heights below this (and above the sand level) are grass, and anything higher is forest.
+const HEIGHTMAP_GRASS_LEVEL: f32 = 0.675;
+
+/// How the pixels of a map image are read.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapImageFormat {
+    /// Greyscale brightness is terrain height, cut into voxel types by height bands.
+    Heightmap,
+    /// Each pixel is the palette colour of a voxel type; the nearest palette colour wins.
+    ColourMap,
+}
+
+fn palette_colour(voxel_type: VoxelType) -> [u8; 3] {
+    match voxel_type {
+        VoxelType::WaterVoxel => [0, 0, 255],
+        VoxelType::SandVoxel => [255, 255, 153],
+        VoxelType::GrassVoxel => [0, 255, 0],
+        VoxelType::ForestVoxel => [0, 100, 25],
+    }
+}
+
+/// Height written to an exported heightmap, chosen from the middle of each type's band.
+fn export_height(voxel_type: VoxelType) -> f32 {
+    match voxel_type {
+        VoxelType::WaterVoxel => 0.2,
+        VoxelType::SandVoxel => 0.475,
+        VoxelType::GrassVoxel => 0.6,
+        VoxelType::ForestVoxel => 0.85,
+    }
+}
+
+const PALETTE: [VoxelType; 4] = [VoxelType::WaterVoxel, VoxelType::SandVoxel, VoxelType::GrassVoxel, VoxelType::ForestVoxel];
+
+/// Builds a world from a map image. Image x maps to grid x and image y to grid z.
+pub fn load_terrain_image(path: &str, format: MapImageFormat) -> Result<WorldMap, image::ImageError> {
+    let image = image::open(path)?;
+    let mut world_map = WorldMap::new(image.width() as i32, image.height() as i32);
+
+    match format {
+        MapImageFormat::Heightmap => {
+            let image = image.to_luma8();
+            for (x, z, Luma([value])) in image.enumerate_pixels() {
+                let height = *value as f32 / 255.0;
+                let voxel_type = if height < HEIGHTMAP_WATER_LEVEL {
+                    VoxelType::WaterVoxel
+                } else if height < HEIGHTMAP_SAND_LEVEL {
+                    VoxelType::SandVoxel
+                } else if height < HEIGHTMAP_GRASS_LEVEL {
+                    VoxelType::GrassVoxel
+                } else {
+                    VoxelType::ForestVoxel
+                };
+                world_map.set_voxel_type((x as i32, z as i32), voxel_type);
+            }
+        }
+        MapImageFormat::ColourMap => {
+            let image = image.to_rgb8();
+            for (x, z, Rgb(colour)) in image.enumerate_pixels() {
+                let voxel_type = PALETTE.into_iter()
+                    .min_by_key(|voxel_type| {
+                        palette_colour(*voxel_type).iter().zip(colour)
+                            .map(|(a, b)| (*a as i32 - *b as i32).pow(2))
+                            .sum::<i32>()
+                    })
+                    .unwrap();
+                world_map.set_voxel_type((x as i32, z as i32), voxel_type);
+            }
+        }
+    }
+
+    Ok(world_map)
+}
+
+/// Writes the terrain to a PNG that `load_terrain_image` reads back to the same voxel types.
+pub fn save_terrain_image(world_map: &WorldMap, path: &str, format: MapImageFormat) -> Result<(), image::ImageError> {
+    let (width, height) = (world_map.width as u32, world_map.height as u32);
+    let voxel_at = |x: u32, z: u32| world_map.voxel_type((x as i32, z as i32)).unwrap_or(VoxelType::WaterVoxel);
+
+    match format {
+        MapImageFormat::Heightmap => {
+            GrayImage::from_fn(width, height, |x, z| Luma([(export_height(voxel_at(x, z)) * 255.0).round() as u8]))
+                .save(path)
+        }
+        MapImageFormat::ColourMap => {
+            RgbImage::from_fn(width, height, |x, z| Rgb(palette_colour(voxel_at(x, z))))
+                .save(path)
+        }
+    }
+}
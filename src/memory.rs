@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ResourceKind {
+    Food,
+    Water,
+}
+
+pub struct RememberedResource {
+    pub kind: ResourceKind,
+    pub location: (i32, i32),
+    /// Ticks since the resource was last seen.
+    pub age: u32,
+}
+
+/// Places an animal has seen food or water, so it can head back once they are out of sight.
+/// Memories fade after `MEMORY_SPAN` ticks, and a place found empty on a return visit is forgotten.
+#[derive(Component, Default)]
+pub struct ResourceMemory {
+    pub entries: Vec<RememberedResource>,
+}
+
+/// Most places an animal can remember at once; the oldest memory makes way for a new one.
+const MEMORY_CAPACITY: usize = 12;
+/// Ticks before an unvisited memory fades.
+const MEMORY_SPAN: u32 = 600;
+/// Sightings this close to an existing memory of the same kind refresh it rather than taking another slot,
+/// so a lake is remembered as a few places rather than every one of its tiles.
+const MEMORY_MERGE_DISTANCE: i32 = 2;
+
+impl ResourceMemory {
+    /// Updates the memories of one kind of resource from what is in view of `centre`.
+    pub fn observe(&mut self, centre: (i32, i32), sight_distance: i32, kind: ResourceKind, seen: &[(i32, i32)]) {
+        let in_view = |location: (i32, i32)| (location.0 - centre.0).abs() <= sight_distance && (location.1 - centre.1).abs() <= sight_distance;
+        self.entries.retain(|entry| entry.kind != kind || !in_view(entry.location) || seen.contains(&entry.location));
+
+        for &location in seen {
+            let nearby = self.entries.iter_mut().find(|entry| {
+                entry.kind == kind
+                    && (entry.location.0 - location.0).abs() <= MEMORY_MERGE_DISTANCE
+                    && (entry.location.1 - location.1).abs() <= MEMORY_MERGE_DISTANCE
+            });
+            match nearby {
+                Some(entry) => entry.age = 0,
+                None => {
+                    if self.entries.len() >= MEMORY_CAPACITY {
+                        if let Some(oldest) = self.entries.iter().enumerate().max_by_key(|(_, entry)| entry.age).map(|(i, _)| i) {
+                            self.entries.swap_remove(oldest);
+                        }
+                    }
+                    self.entries.push(RememberedResource { kind, location, age: 0 });
+                }
+            }
+        }
+    }
+
+    /// Ages every memory by a tick, forgetting those that have faded.
+    pub fn age(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.age += 1;
+        }
+        self.entries.retain(|entry| entry.age <= MEMORY_SPAN);
+    }
+
+    pub fn nearest(&self, kind: ResourceKind, location: (i32, i32)) -> Option<(i32, i32)> {
+        self.entries.iter()
+            .filter(|entry| entry.kind == kind)
+            .map(|entry| entry.location)
+            .min_by_key(|remembered| (remembered.0 - location.0).abs() + (remembered.1 - location.1).abs())
+    }
+}
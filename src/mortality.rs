@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::fmt::Write;
+
+use crate::scenario::Scenario;
+
+/// Chance of dying of old age at each age, rolled once every age tick.
+#[derive(Resource, Deserialize, Clone, Debug)]
+pub enum MortalityModel {
+    /// No deaths before `onset`, then a chance of 1 in (`max_age` - age) each age tick until death is certain at `max_age`.
+    AgeRoll { onset: u32, max_age: u32 },
+    /// Hazard of `baseline * e^(rate * age)`, growing exponentially with age.
+    Gompertz { baseline: f32, rate: f32 },
+    /// The same hazard at every age, so lifespans are geometrically distributed.
+    ConstantHazard { hazard: f32 },
+    /// Chance of dying at each age from 0 upwards. Animals that outlive the table die.
+    LifeTable { death_chances: Vec<f32> },
+}
+
+impl Default for MortalityModel {
+    fn default() -> Self {
+        MortalityModel::AgeRoll { onset: 50, max_age: 100 }
+    }
+}
+
+/// Oldest age covered by the survival export, in case a model lets animals live forever.
+const MAX_EXPORT_AGE: u32 = 1000;
+/// Expected survival below which the export stops, once no animal has been observed that old.
+const EXPORT_SURVIVAL_CUTOFF: f32 = 0.001;
+const SURVIVAL_EXPORT_PATH: &str = "survival_curve.csv";
+
+impl MortalityModel {
+    /// Chance that an animal that has just turned `age` dies this age tick.
+    pub fn death_chance(&self, age: u32) -> f32 {
+        match self {
+            MortalityModel::AgeRoll { onset, max_age } => {
+                if age >= *max_age {
+                    1.0
+                } else if age >= *onset {
+                    1.0 / (max_age - age) as f32
+                } else {
+                    0.0
+                }
+            }
+            MortalityModel::Gompertz { baseline, rate } => 1.0 - (-baseline * (rate * age as f32).exp()).exp(),
+            MortalityModel::ConstantHazard { hazard } => 1.0 - (-hazard).exp(),
+            MortalityModel::LifeTable { death_chances } => death_chances.get(age as usize).copied().unwrap_or(1.0),
+        }
+        .clamp(0.0, 1.0)
+    }
+
+    pub fn dies(&self, age: u32, rng: &mut impl Rng) -> bool {
+        rng.gen::<f32>() < self.death_chance(age)
+    }
+
+    /// Share of newborns expected to still be alive at each age from 0 to `max_age`. Animals roll
+    /// against the death chance of the age they have reached before growing a year older.
+    pub fn expected_survival(&self, max_age: u32) -> Vec<f32> {
+        let mut survival = vec![1.0];
+        for age in 0..max_age {
+            let previous = survival[survival.len() - 1];
+            survival.push(previous * (1.0 - self.death_chance(age)));
+        }
+        survival
+    }
+}
+
+/// How many animals reached each age and how many of those died of old age there, for comparing
+/// the observed survival curve against the model.
+///
+/// Animals that die of something else, or are still alive, simply stop being counted, so the
+/// observed curve is a Kaplan-Meier estimate of survival against old age alone.
+#[derive(Resource, Default)]
+pub struct SurvivalStats {
+    pub at_risk: Vec<u32>,
+    pub deaths: Vec<u32>,
+}
+
+impl SurvivalStats {
+    /// Records an animal's mortality roll at `age`, before it grows a year older, and whether it was killed.
+    pub fn record(&mut self, age: u32, died: bool) {
+        let age = age as usize;
+        if self.at_risk.len() <= age {
+            self.at_risk.resize(age + 1, 0);
+            self.deaths.resize(age + 1, 0);
+        }
+        self.at_risk[age] += 1;
+        if died {
+            self.deaths[age] += 1;
+        }
+    }
+
+    /// Observed share of animals surviving to each age from 0 to `max_age`.
+    pub fn observed_survival(&self, max_age: u32) -> Vec<f32> {
+        let mut survival = vec![1.0];
+        for age in 0..max_age as usize {
+            let previous = survival[survival.len() - 1];
+            let at_risk = self.at_risk.get(age).copied().unwrap_or(0);
+            let deaths = self.deaths.get(age).copied().unwrap_or(0);
+            survival.push(if at_risk > 0 { previous * (1.0 - deaths as f32 / at_risk as f32) } else { previous });
+        }
+        survival
+    }
+
+    pub fn export(&self, model: &MortalityModel) {
+        match std::fs::write(SURVIVAL_EXPORT_PATH, self.survival_csv(model)) {
+            Ok(()) => println!("Exported survival curves to {}", SURVIVAL_EXPORT_PATH),
+            Err(error) => println!("Failed to export survival curves to {}: {}", SURVIVAL_EXPORT_PATH, error),
+        }
+    }
+
+    fn survival_csv(&self, model: &MortalityModel) -> String {
+        // One past the oldest age rolled at, so the curve shows the outcome of the last roll.
+        let oldest_observed = self.at_risk.len() as u32;
+        let expected = model.expected_survival(MAX_EXPORT_AGE.max(oldest_observed));
+        let horizon = expected.iter()
+            .position(|&survival| survival < EXPORT_SURVIVAL_CUTOFF)
+            .map_or(MAX_EXPORT_AGE, |age| age as u32)
+            .max(oldest_observed);
+        let observed = self.observed_survival(horizon);
+
+        let mut csv = String::from("age,at_risk,deaths,expected_survival,observed_survival\n");
+        for age in 0..=horizon as usize {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                age,
+                self.at_risk.get(age).copied().unwrap_or(0),
+                self.deaths.get(age).copied().unwrap_or(0),
+                expected[age],
+                observed[age],
+            );
+        }
+        csv
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let mortality_model = app.world().resource::<Scenario>().rabbit_mortality.clone();
+    app
+        .insert_resource(mortality_model)
+        .init_resource::<SurvivalStats>();
+}
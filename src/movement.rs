@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::f32::consts::PI;
+
+use crate::{
+    frame_manager::FrameControl,
+    scenario::Scenario,
+    world_setup::{VoxelType, WorldMap},
+};
+
+#[derive(Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Connectivity {
+    /// Moves along the axes only.
+    Four,
+    /// Moves along the axes and diagonals.
+    Eight,
+}
+
+/// How animals get around the grid, set from the scenario's `movement` section.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct MovementConfig {
+    pub connectivity: Connectivity,
+    /// Stride spent entering a grass tile.
+    pub grass_cost: f32,
+    /// Stride spent entering a sand tile; loose sand is slow going.
+    pub sand_cost: f32,
+    /// Stride spent entering a forest tile, pushing through undergrowth.
+    pub forest_cost: f32,
+    /// Most animals allowed on one tile, or `None` for no limit. Burrows are exempt.
+    pub tile_capacity: Option<u32>,
+    /// Number of recent tiles kept in each animal's `MovementHistory`.
+    pub history_length: usize,
+    /// Draws every animal's recent path.
+    pub draw_history: bool,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        MovementConfig {
+            connectivity: Connectivity::Eight,
+            grass_cost: 1.0,
+            sand_cost: 1.5,
+            forest_cost: 1.25,
+            tile_capacity: Some(2),
+            history_length: 20,
+            draw_history: false,
+        }
+    }
+}
+
+impl MovementConfig {
+    /// Stride spent entering a tile of this type, or `None` if it cannot be walked on.
+    pub fn move_cost(&self, voxel_type: VoxelType) -> Option<f32> {
+        match voxel_type {
+            VoxelType::GrassVoxel => Some(self.grass_cost),
+            VoxelType::SandVoxel => Some(self.sand_cost),
+            VoxelType::ForestVoxel => Some(self.forest_cost),
+            VoxelType::WaterVoxel => None,
+        }
+    }
+
+    /// Number of moves between two tiles on an empty map.
+    pub fn distance(&self, a: (i32, i32), b: (i32, i32)) -> i32 {
+        let (dx, dz) = ((a.0 - b.0).abs(), (a.1 - b.1).abs());
+        match self.connectivity {
+            Connectivity::Four => dx + dz,
+            Connectivity::Eight => dx.max(dz),
+        }
+    }
+
+    /// Walkable tiles one move away from `location`.
+    pub fn neighbours(&self, world_map: &WorldMap, location: (i32, i32)) -> Vec<(i32, i32)> {
+        self.adjacent(location)
+            .filter(|&tile| world_map.is_walkable(tile))
+            .collect()
+    }
+
+    /// Neighbouring water tiles a fish could swim into.
+    pub fn swim_neighbours(&self, world_map: &WorldMap, location: (i32, i32)) -> Vec<(i32, i32)> {
+        self.adjacent(location)
+            .filter(|&tile| world_map.is_swimmable(tile))
+            .collect()
+    }
+
+    fn adjacent(&self, location: (i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+        let offsets: &[(i32, i32)] = match self.connectivity {
+            Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            Connectivity::Eight => &[(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)],
+        };
+        offsets.iter().map(move |offset| (location.0 + offset.0, location.1 + offset.1))
+    }
+}
+
+/// Number of animals standing on each tile this tick. Moves are granted in turn, so when two
+/// animals want the last free spot on a tile the first one to move takes it.
+pub struct Occupancy {
+    counts: HashMap<(i32, i32), u32>,
+    capacity: Option<u32>,
+}
+
+impl Occupancy {
+    pub fn new(capacity: Option<u32>, locations: impl Iterator<Item = (i32, i32)>) -> Self {
+        let mut counts = HashMap::new();
+        for location in locations {
+            *counts.entry(location).or_insert(0) += 1;
+        }
+        Occupancy { counts, capacity }
+    }
+
+    pub fn has_room(&self, world_map: &WorldMap, location: (i32, i32)) -> bool {
+        let Some(capacity) = self.capacity else {
+            return true;
+        };
+        world_map.burrow_at(location).is_some() || self.counts.get(&location).copied().unwrap_or(0) < capacity
+    }
+
+    pub fn relocate(&mut self, from: (i32, i32), to: (i32, i32)) {
+        if let Some(count) = self.counts.get_mut(&from) {
+            *count = count.saturating_sub(1);
+        }
+        *self.counts.entry(to).or_insert(0) += 1;
+    }
+}
+
+/// Recent tiles an animal stood on, oldest first, for debugging its decisions.
+#[derive(Component, Default)]
+pub struct MovementHistory(pub VecDeque<(i32, i32)>);
+
+impl MovementHistory {
+    pub fn record(&mut self, location: (i32, i32), history_length: usize) {
+        self.0.push_back(location);
+        while self.0.len() > history_length {
+            self.0.pop_front();
+        }
+    }
+}
+
+/// Plays an animal's move between grid tiles out smoothly over one simulation tick,
+/// keeping the visual transform separate from the logical grid location.
+#[derive(Component)]
+pub struct MovementTween {
+    pub from: Vec3,
+    pub to: Vec3,
+    /// From 0.0 at `from` to 1.0 at `to`.
+    pub progress: f32,
+    /// Number of hops to make on the way, one per tile covered.
+    pub hops: u32,
+}
+
+impl MovementTween {
+    pub fn new(position: Vec3) -> Self {
+        MovementTween {
+            from: position,
+            to: position,
+            progress: 1.0,
+            hops: 0,
+        }
+    }
+
+    /// Starts a new move from wherever the last one was headed.
+    pub fn move_to(&mut self, to: Vec3, hops: u32) {
+        self.from = self.to;
+        self.to = to;
+        self.progress = 0.0;
+        self.hops = hops;
+    }
+}
+
+/// Height of a hop at its peak.
+const HOP_HEIGHT: f32 = 0.2;
+
+pub(super) fn plugin(app: &mut App) {
+    let movement_config = app.world().resource::<Scenario>().movement.clone();
+    app
+        .insert_resource(movement_config)
+        .add_systems(Update, (animate_movement, draw_movement_history));
+}
+
+/// Next tile on the way from `location` to `target`: the free neighbour that gets closest,
+/// preferring cheaper ground when two are equally close.
+pub fn step_towards(
+    world_map: &WorldMap,
+    config: &MovementConfig,
+    occupancy: &Occupancy,
+    location: (i32, i32),
+    target: (i32, i32),
+) -> Option<(i32, i32)> {
+    let current_distance = config.distance(location, target);
+    config.neighbours(world_map, location)
+        .into_iter()
+        .filter(|&tile| config.distance(tile, target) < current_distance && occupancy.has_room(world_map, tile))
+        .min_by(|&a, &b| {
+            let cost = |tile| world_map.voxel_type(tile).and_then(|voxel_type| config.move_cost(voxel_type)).unwrap_or(f32::MAX);
+            let straightness = |tile: (i32, i32)| (tile.0 - target.0).abs() + (tile.1 - target.1).abs();
+            config.distance(a, target).cmp(&config.distance(b, target))
+                .then(cost(a).total_cmp(&cost(b)))
+                .then(straightness(a).cmp(&straightness(b)))
+        })
+}
+
+fn animate_movement(
+    time: Res<Time>,
+    frame_control: Res<FrameControl>,
+    mut tween_query: Query<(&mut MovementTween, &mut Transform)>,
+) {
+    let tick_length = frame_control.timer.duration().as_secs_f32();
+    for (mut tween, mut transform) in tween_query.iter_mut() {
+        if tween.progress >= 1.0 {
+            continue;
+        }
+        tween.progress = (tween.progress + time.delta_secs() / tick_length).min(1.0);
+
+        let hop = if tween.progress < 1.0 {
+            (tween.progress * tween.hops as f32 * PI).sin().abs() * HOP_HEIGHT
+        } else {
+            0.0
+        };
+        transform.translation = tween.from.lerp(tween.to, tween.progress) + Vec3::Y * hop;
+
+        let direction = tween.to - tween.from;
+        if direction.x != 0.0 || direction.z != 0.0 {
+            transform.rotation = Quat::from_rotation_y(direction.x.atan2(direction.z));
+        }
+    }
+}
+
+fn draw_movement_history(
+    config: Res<MovementConfig>,
+    world_map_query: Query<&WorldMap>,
+    history_query: Query<&MovementHistory>,
+    mut gizmos: Gizmos,
+) {
+    if !config.draw_history {
+        return;
+    }
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+    for history in history_query.iter() {
+        let path = history.0.iter().map(|&location| world_map.to_world(location, VoxelType::GrassVoxel.surface_height() + 0.1));
+        gizmos.linestrip(path, Color::srgb(1.0, 0.5, 0.0));
+    }
+}
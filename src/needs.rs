@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    frame_manager::FrameControl,
+    rabbit::Rabbit,
+    scenario::Scenario,
+};
+
+/// How fast one species gets hungry and thirsty, and how it eats and drinks.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct NeedRates {
+    /// Hunger and thirst of a completely fed and watered animal.
+    pub max: f32,
+    /// Hunger lost every tick, whatever the animal is doing.
+    pub hunger_decay: f32,
+    /// Thirst lost every tick, whatever the animal is doing.
+    pub thirst_decay: f32,
+    /// Share of `max` at which a feeding animal has had enough and stops.
+    pub satiation: f32,
+    /// Biomass grazed per tick of eating from a fully grown plant; sparser plants give smaller bites.
+    pub bite_size: f32,
+    /// Thirst quenched per tick of drinking.
+    pub drink_rate: f32,
+}
+
+impl Default for NeedRates {
+    fn default() -> Self {
+        NeedRates {
+            max: 100.0,
+            hunger_decay: 1.0,
+            thirst_decay: 1.0,
+            satiation: 0.9,
+            bite_size: 2.0,
+            drink_rate: 5.0,
+        }
+    }
+}
+
+impl NeedRates {
+    pub fn is_satiated(&self, need: f32) -> bool {
+        need >= self.max * self.satiation
+    }
+}
+
+/// Need rates of every species, set from the scenario's `needs` section.
+#[derive(Resource, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct NeedsConfig {
+    pub rabbit: NeedRates,
+}
+
+/// A meal or drink that lasts several ticks, until the animal is satiated or is disturbed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Feeding {
+    /// Grazing the plant on the animal's tile.
+    Grazing(Entity),
+    /// Drinking from a neighbouring water tile.
+    Drinking((i32, i32)),
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let needs_config = app.world().resource::<Scenario>().needs.clone();
+    app
+        .insert_resource(needs_config)
+        .add_systems(Update, decay_rabbit_needs);
+}
+
+fn decay_rabbit_needs(
+    frame_control: Res<FrameControl>,
+    needs_config: Res<NeedsConfig>,
+    mut rabbit_query: Query<&mut Rabbit>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let rates = &needs_config.rabbit;
+    for mut rabbit in rabbit_query.iter_mut() {
+        rabbit.hunger = (rabbit.hunger - rates.hunger_decay).max(0.0);
+        rabbit.thirst = (rabbit.thirst - rates.thirst_decay).max(0.0);
+    }
+}
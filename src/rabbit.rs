@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 use rand::Rng;
+use rand::seq::SliceRandom;
 
 use crate::{
-    foliage::{Foliage, FoliageConsumedEvent}, frame_manager::FrameControl, world_setup::{Voxel, VoxelType, WorldMap, WorldMapDataSetEvent}
+    burrow::Burrow, disease::DiseaseState, life::{LifeConfig, LifeStage}, mortality::{MortalityModel, SurvivalStats}, carcass::CARCASS_MEAT, health::{Health, InjuryEvent}, day_night::{DayPhase, TimeOfDay}, seasons::SeasonCycle, soil::FertiliseSoilEvent, foliage::{Foliage, FoliageConsumedEvent, PlantSpecies}, frame_manager::FrameControl, memory::{ResourceKind, ResourceMemory}, needs::{Feeding, NeedsConfig}, social::SocialConfig, movement::{step_towards, MovementConfig, MovementHistory, MovementTween, Occupancy}, world_setup::{ChunkLoader, VoxelType, WorldMap, WorldMapDataSetEvent}
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -10,23 +11,151 @@ pub(super) fn plugin(app: &mut App) {
         .add_event::<RabbitBreedingEvent>()
         .add_event::<UpdateNearbyResourcesEvent>()
         .insert_resource(RabbitResource { rabbits: Vec::new() })
-        .add_systems(Update, (spawn_initial_rabbits, rabbit_movement, update_rabbit_nearby_resources, spawn_new_rabbit,update_rabbit_partner_list, rabbit_age_tick, update_details_on_breeding));
+        .add_systems(Startup, setup_rabbit_assets)
+        .add_systems(Update, (spawn_initial_rabbits, rabbit_movement, update_rabbit_nearby_resources, spawn_new_rabbit,update_rabbit_neighbours, rabbit_age_tick, update_details_on_breeding, refresh_requested_nearby_resources, rabbit_droppings));
 }
 
 #[derive(Component, Clone)]
 pub struct Rabbit {
     pub id: u32,
-    pub hunger: u32,
-    pub thirst: u32,
+    /// Fullness from 0.0 (starving) up to the species' maximum.
+    pub hunger: f32,
+    /// Hydration from 0.0 (parched) up to the species' maximum.
+    pub thirst: f32,
+    /// Meal or drink the rabbit is in the middle of.
+    pub feeding: Option<Feeding>,
     pub location: (i32, i32),
     pub plants_in_range: Vec<Entity>,
-    pub water_in_range: Vec<Entity>,
+    pub water_in_range: Vec<(i32, i32)>,
     pub partner_in_range: Vec<Entity>,
+    /// Every other rabbit within sight; `partner_in_range` is the subset ready to mate.
+    pub neighbours_in_range: Vec<Entity>,
     pub sight_distance: u32,
-    pub satisfaction_threshold: u32,
-    pub full_threshold: u32,
+    pub satisfaction_threshold: f32,
+    pub full_threshold: f32,
     pub age: u32,
+    pub stage: LifeStage,
+    /// Fraction of its adult `body_size` the rabbit has grown to.
+    pub growth: f32,
+    /// Parent a kit follows and suckles from until it is weaned.
+    pub mother: Option<Entity>,
     pub mating_cooldown: u32,
+    /// Half extents of the rabbit's body once fully grown, applied as a scale on the shared rabbit mesh.
+    pub body_size: Vec3,
+    /// Index into `RabbitAssets::coats`.
+    pub coat: usize,
+    /// Burrow the rabbit sleeps in, shared with its family.
+    pub home: Option<Entity>,
+    /// Whether the rabbit is down in its burrow, out of sight of predators.
+    pub in_burrow: bool,
+    /// Stamina from 0.0 (exhausted) to `MAX_ENERGY`, spent on every move and regained by resting.
+    pub energy: f32,
+    /// Heritable speed gene around 1.0. Faster rabbits burn more energy per move.
+    pub speed: f32,
+    /// Set while the rabbit is catching its breath, until its energy is back to full.
+    pub resting: bool,
+    pub disease: DiseaseState,
+    /// Heritable resistance to infection from 0.0 (none) to 1.0 (immune).
+    pub disease_resistance: f32,
+    /// Ticks left before the rabbit calms down after a predator was spotted.
+    pub alarmed: u32,
+    /// Where the predator that raised the alarm was seen.
+    pub alarm_source: Option<(i32, i32)>,
+    /// Movement carried over between ticks, so a rabbit with speed 1.5 covers three grass tiles every two ticks.
+    /// Each tile entered costs its biome's share of stride.
+    pub stride: f32,
+}
+
+impl Rabbit {
+    pub fn new(id: u32, location: (i32, i32)) -> Self {
+        let mut rng = rand::thread_rng();
+        Rabbit {
+            id,
+            hunger: 50.0,
+            thirst: 50.0,
+            feeding: None,
+            location,
+            plants_in_range: Vec::new(),
+            water_in_range: Vec::new(),
+            partner_in_range: Vec::new(),
+            neighbours_in_range: Vec::new(),
+            sight_distance: 3,
+            satisfaction_threshold: 50.0,
+            full_threshold: 70.0,
+            age: 0,
+            stage: LifeStage::Kit,
+            growth: 1.0,
+            mother: None,
+            mating_cooldown: 0,
+            body_size: Vec3::new(rng.gen_range(0.2..0.3), rng.gen_range(0.2..0.3), rng.gen_range(0.2..0.3)),
+            coat: rng.gen_range(0..RABBIT_COAT_COUNT),
+            home: None,
+            in_burrow: false,
+            energy: MAX_ENERGY,
+            speed: rng.gen_range(0.8..1.2),
+            resting: false,
+            stride: 0.0,
+            alarmed: 0,
+            alarm_source: None,
+            disease: DiseaseState::Susceptible,
+            disease_resistance: rng.gen_range(0.0..0.3),
+        }
+    }
+
+    /// Sets the rabbit's age, along with the life stage and size that go with it.
+    pub fn set_age(&mut self, age: u32, config: &LifeConfig) {
+        self.age = age;
+        self.stage = config.stage(age);
+        self.growth = config.growth(age);
+    }
+
+    /// Current half extents of the body, smaller than `body_size` while the rabbit is still growing.
+    pub fn size(&self) -> Vec3 {
+        self.body_size * self.growth
+    }
+
+    /// Tiles the rabbit can see; kits and elders see less far.
+    pub fn sight(&self) -> i32 {
+        (self.sight_distance as i32 + self.stage.sight_modifier()).max(1)
+    }
+
+    /// Energy spent on a single move; heavier and faster rabbits tire sooner.
+    pub fn move_cost(&self) -> f32 {
+        let size = self.size();
+        MOVE_ENERGY_COST * self.speed * (size.x * size.y * size.z) / 0.25_f32.powi(3)
+    }
+
+    /// Adds this tick's movement to the stride, banking at most `MAX_STRIDE` while the rabbit is held up.
+    fn gather_stride(&mut self) {
+        self.stride = (self.stride + self.speed * self.stage.speed_factor()).min(MAX_STRIDE);
+    }
+
+    /// Moves onto a neighbouring tile if the stride left this tick covers its cost, spending energy in proportion.
+    fn take_step(&mut self, next: (i32, i32), world_map: &WorldMap, config: &MovementConfig, occupancy: &mut Occupancy) -> bool {
+        let Some(cost) = world_map.voxel_type(next).and_then(|voxel_type| config.move_cost(voxel_type)) else {
+            return false;
+        };
+        if self.stride < cost {
+            return false;
+        }
+        self.stride -= cost;
+        self.energy = (self.energy - self.move_cost() * cost).max(0.0);
+        occupancy.relocate(self.location, next);
+        self.location = next;
+        true
+    }
+
+    /// Where the rabbit stands in the world, feet on the surface of its tile.
+    pub fn world_position(&self, world_map: &WorldMap) -> Vec3 {
+        let surface_height = world_map.voxel_type(self.location).unwrap_or(VoxelType::GrassVoxel).surface_height();
+        world_map.to_world(self.location, surface_height + self.size().y)
+    }
+
+    /// Meat left behind when the rabbit dies, scaled by its body volume against an average 0.25 half extent rabbit.
+    pub fn carcass_meat(&self) -> f32 {
+        let size = self.size();
+        CARCASS_MEAT * (size.x * size.y * size.z) / 0.25_f32.powi(3)
+    }
 }
 
 pub enum RabbitPriorityMovement {
@@ -34,6 +163,11 @@ pub enum RabbitPriorityMovement {
     Water,
     Food,
     Random,
+    Group,
+    Flee,
+    Home,
+    Rest,
+    Follow,
     None,
 }
 
@@ -48,6 +182,15 @@ pub struct RabbitResource {
     pub rabbits: Vec<Entity>,
 }
 
+/// Mesh and coat materials shared by every rabbit so they can be drawn as instances.
+#[derive(Resource)]
+pub struct RabbitAssets {
+    pub mesh: Handle<Mesh>,
+    pub coats: Vec<Handle<StandardMaterial>>,
+    /// Sickly green tinted versions of `coats`, worn while a rabbit is infected.
+    pub infected_coats: Vec<Handle<StandardMaterial>>,
+}
+
 #[derive(Default)]
 pub struct RabbitAgeLocalCounter {
     pub counter: u32,
@@ -55,74 +198,108 @@ pub struct RabbitAgeLocalCounter {
 
 
 const INITIAL_RABBIT_POPULATION: u32 = 12;
-
-pub fn spawn_initial_rabbits(
+const RABBIT_COAT_COUNT: usize = 8;
+/// Nutrients in a single dropping, and the chance per tick of a rabbit leaving one.
+const DROPPING_NUTRIENTS: f32 = 0.02;
+const DROPPING_CHANCE: f32 = 0.05;
+pub const MAX_ENERGY: f32 = 100.0;
+/// Most stride a rabbit can bank, enough to eventually cross the costliest ground.
+const MAX_STRIDE: f32 = 2.0;
+/// Energy an average rabbit spends per move across grass.
+const MOVE_ENERGY_COST: f32 = 1.5;
+/// Energy regained per tick of rest.
+const REST_ENERGY_GAIN: f32 = 4.0;
+/// Below this energy a rabbit stops to rest unless it is close to starving.
+const TIRED_ENERGY: f32 = 20.0;
+/// Hunger and thirst a kit gains each tick it suckles beside its mother.
+const NURSING_GAIN: f32 = 3.0;
+
+fn setup_rabbit_assets(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut rng = rand::thread_rng();
+
+    let coat_colours: Vec<(f32, f32, f32)> = (0..RABBIT_COAT_COUNT).map(|_| {
+        (rng.gen_range(0.5..1.0), rng.gen_range(0.3..0.6), rng.gen_range(0.15..0.3))
+    }).collect();
+
+    let coats = coat_colours.iter().map(|&(rand_r, rand_g, rand_b)| {
+        materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(rand_r, rand_g, rand_b),
+            ..default()
+        })
+    }).collect();
+    let infected_coats = coat_colours.iter().map(|&(rand_r, rand_g, rand_b)| {
+        materials.add(StandardMaterial {
+            base_color: Color::linear_rgb(rand_r * 0.5, (rand_g + 0.4).min(1.0), rand_b * 0.5),
+            ..default()
+        })
+    }).collect();
+
+    commands.insert_resource(RabbitAssets {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        coats,
+        infected_coats,
+    });
+}
+
+pub fn spawn_rabbit(
+    commands: &mut Commands,
+    rabbit_assets: &RabbitAssets,
+    world_map: &WorldMap,
+    rabbit: Rabbit,
+) -> Entity {
+    let position = rabbit.world_position(world_map);
+    let transform = Transform::from_translation(position)
+        .with_scale(rabbit.size() * 2.0);
+
+    commands.spawn((
+        Mesh3d(rabbit_assets.mesh.clone()),
+        MeshMaterial3d(rabbit_assets.coats[rabbit.coat].clone()),
+        rabbit,
+        transform,
+        MovementTween::new(position),
+        MovementHistory::default(),
+        ResourceMemory::default(),
+        Health::default(),
+        ChunkLoader,
+    )).id()
+}
+
+pub fn spawn_initial_rabbits(
+    mut commands: Commands,
+    rabbit_assets: Res<RabbitAssets>,
     mut events: EventReader<WorldMapDataSetEvent>,
     world_map_query: Query<&WorldMap>,
-    voxel_query: Query<&Voxel>,
+    life_config: Res<LifeConfig>,
     mut rabbit_resource: ResMut<RabbitResource>,
 ) {
     for active_event in events.read() {
         match active_event {
             WorldMapDataSetEvent => {
                 let world_map = world_map_query.single();
-                for i in 0..INITIAL_RABBIT_POPULATION {
-
-                    let mut rng = rand::thread_rng();
-            
-                    let mut x: i32;
-                    let mut z: i32;
-
-                    loop {
-                        x = rng.gen_range(0..world_map.width);
-                        z = rng.gen_range(0..world_map.height);
-
-                        println!("{}, {}", x, z);
-
-                        let Ok(current_voxel) = voxel_query.get(world_map.map[x as usize][z as usize]) else {
-                            continue;
-                        };
 
-                        if current_voxel.voxel_type == VoxelType::GrassVoxel {
-                            break;
+                let mut grass = Vec::new();
+                for x in 0..world_map.width {
+                    for z in 0..world_map.height {
+                        if world_map.voxel_type((x, z)) == Some(VoxelType::GrassVoxel) {
+                            grass.push((x, z));
                         }
                     }
-            
-                    let rabbit = Rabbit {
-                        id: i,
-                        hunger: 50,
-                        thirst: 50,
-                        location: (x, z),
-                        plants_in_range: Vec::new(),
-                        water_in_range: Vec::new(),
-                        partner_in_range: Vec::new(),
-                        sight_distance: 3,
-                        satisfaction_threshold: 50,
-                        full_threshold: 70,
-                        age: 0,
-                        mating_cooldown: 0,
-                    };
+                }
+                if grass.is_empty() {
+                    println!("No grass tiles to place the initial rabbits on!");
+                    continue;
+                }
 
-                    let rand_r = rng.gen_range(0.5..1.0);
-                    let rand_g = rng.gen_range(0.3..0.6);
-                    let rand_b = rng.gen_range(0.15..0.3);
-            
-                    let rabbit_entity = commands.spawn((
-                        rabbit,
-                        Mesh3d(meshes.add(Cuboid {
-                            half_size: Vec3::new(rng.gen_range(0.2..0.3), rng.gen_range(0.2..0.3), rng.gen_range(0.2..0.3)),
-                            ..default()
-                        })),
-                        MeshMaterial3d(materials.add(StandardMaterial {
-                            base_color: Color::linear_rgb(rand_r, rand_g, rand_b),
-                            ..default()
-                        })),
-                        Transform::from_translation(Vec3::new((x - 30) as f32, -0.175, (z - 30) as f32)),
-                        
-                    )).id();
+                let mut rng = rand::thread_rng();
+                for (i, &location) in grass.choose_multiple(&mut rng, INITIAL_RABBIT_POPULATION as usize).enumerate() {
+                    // The founding population starts out fully grown.
+                    let mut rabbit = Rabbit::new(i as u32, location);
+                    rabbit.set_age(life_config.adult_age, &life_config);
+                    let rabbit_entity = spawn_rabbit(&mut commands, &rabbit_assets, world_map, rabbit);
 
                     rabbit_resource.rabbits.push(rabbit_entity);
                 }
@@ -132,24 +309,32 @@ pub fn spawn_initial_rabbits(
     
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_new_rabbit(
     mut events: EventReader<RabbitBreedingEvent>,
     mut commands: Commands,
     rabbit_query: Query<&Rabbit>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    burrow_query: Query<&Burrow>,
+    world_map_query: Query<&WorldMap>,
+    rabbit_assets: Res<RabbitAssets>,
+    season_cycle: Res<SeasonCycle>,
+    life_config: Res<LifeConfig>,
     mut rabbit_resource: ResMut<RabbitResource>,
 ) {
 
     for active_event in events.read() {
         match active_event {
             RabbitBreedingEvent(entity1, entity2) => {
-                let rabbit1 = rabbit_query.get(*entity1).unwrap();
-                let rabbit2 = rabbit_query.get(*entity2).unwrap();
+                // Either parent may have died since the event was sent.
+                let (Ok(rabbit1), Ok(rabbit2)) = (rabbit_query.get(*entity1), rabbit_query.get(*entity2)) else {
+                    continue;
+                };
+
+                let world_map = world_map_query.single();
 
                 let mut rng = rand::thread_rng();
 
-                let baby_count = rng.gen_range(1..3);
+                let baby_count = rng.gen_range(season_cycle.season.litter_size());
 
                 if rabbit_resource.rabbits.len() as usize > 200 {
                     println!("Rabbit population is at maximum capacity!");
@@ -158,40 +343,25 @@ pub fn spawn_new_rabbit(
 
                 println!("New rabbit!");
 
-                for _ in 0..baby_count {
-
-                    
+                // Kits are born in the family burrow and share it with their parents.
+                let home = rabbit1.home.or(rabbit2.home);
+                let birthplace = home
+                    .and_then(|home| burrow_query.get(home).ok())
+                    .map_or((rabbit1.location.0, rabbit2.location.1), |burrow| burrow.location);
 
-                    let rand_r = rng.gen_range(0.5..1.0);
-                    let rand_g = rng.gen_range(0.3..0.6);
-                    let rand_b = rng.gen_range(0.15..0.3);
-
-                    let rabbit_entity = commands.spawn((
-                        Rabbit {
-                            id: rabbit1.id + rabbit2.id,
-                            hunger: 50,
-                            thirst: 50,
-                            location: (rabbit1.location.0, rabbit2.location.1),
-                            plants_in_range: Vec::new(),
-                            water_in_range: Vec::new(),
-                            partner_in_range: Vec::new(),
-                            sight_distance: 3,
-                            satisfaction_threshold: 50,
-                            full_threshold: 70,
-                            age: 0,
-                            mating_cooldown: 0,
-                        },
-                        Mesh3d(meshes.add(Cuboid {
-                            half_size: Vec3::new(rng.gen_range(0.2..0.3), rng.gen_range(0.2..0.3), rng.gen_range(0.2..0.3)),
-                            ..default()
-                        })),
-                        MeshMaterial3d(materials.add(StandardMaterial {
-                            base_color: Color::linear_rgb(rand_r, rand_g, rand_b),
-                            ..default()
-                        })),
-                        Transform::from_translation(Vec3::new((rabbit1.location.0 - 30) as f32, -0.175, (rabbit2.location.1 - 30) as f32)),
-                        
-                    )).id();
+                for _ in 0..baby_count {
+                    let mut kit = Rabbit::new(rabbit1.id + rabbit2.id, birthplace);
+                    kit.set_age(0, &life_config);
+                    kit.mother = Some(*entity1);
+                    kit.home = home;
+                    kit.speed = ((rabbit1.speed + rabbit2.speed) / 2.0 + rng.gen_range(-0.05..0.05)).clamp(0.5, 1.5);
+                    kit.disease_resistance = ((rabbit1.disease_resistance + rabbit2.disease_resistance) / 2.0 + rng.gen_range(-0.05..0.05)).clamp(0.0, 1.0);
+                    let rabbit_entity = spawn_rabbit(
+                        &mut commands,
+                        &rabbit_assets,
+                        world_map,
+                        kit,
+                    );
 
                     rabbit_resource.rabbits.push(rabbit_entity);
                 }
@@ -209,10 +379,17 @@ fn update_details_on_breeding(
     for active_event in events.read() {
         match active_event {
             RabbitBreedingEvent(entity1, entity2) => {
-                let mut rabbits = rabbit_query.get_many_mut([*entity1, *entity2]).unwrap();
+                let Ok(mut rabbits) = rabbit_query.get_many_mut([*entity1, *entity2]) else {
+                    continue;
+                };
 
                 rabbits[0].mating_cooldown = 20;
                 rabbits[1].mating_cooldown = 20;
+
+                // A homeless partner moves into the other's burrow.
+                let home = rabbits[0].home.or(rabbits[1].home);
+                rabbits[0].home = rabbits[0].home.or(home);
+                rabbits[1].home = rabbits[1].home.or(home);
             }
         }
     }
@@ -220,16 +397,25 @@ fn update_details_on_breeding(
 
 
 
+/// A rabbit's decision for the tick: its priority, the direction it moves in and the plant it bit, if any.
+type RabbitMove = (Entity, RabbitPriorityMovement, i32, i32, Option<(Entity, PlantSpecies, f32)>);
+
+#[allow(clippy::too_many_arguments)]
 fn rabbit_movement(
-    mut commands: Commands,
     frame_control: Res<FrameControl>,
-    mut rabbit_query: Query<(Entity, &mut Rabbit, &mut Transform), With<Rabbit>>,
+    time_of_day: Res<TimeOfDay>,
+    season_cycle: Res<SeasonCycle>,
+    movement_config: Res<MovementConfig>,
+    social_config: Res<SocialConfig>,
+    needs_config: Res<NeedsConfig>,
+    mut rabbit_query: Query<(Entity, &mut Rabbit, &mut MovementTween, &mut MovementHistory, &ResourceMemory), With<Rabbit>>,
     //partner_query: Query<&Rabbit>,
-    voxel_query: Query<&Voxel>,
     foliage_query: Query<&Foliage>,
+    burrow_query: Query<&Burrow>,
     world_map_query: Query<&WorldMap>,
     mut event_writer: EventWriter<FoliageConsumedEvent>,
     mut rabbit_breeding_event_writer: EventWriter<RabbitBreedingEvent>,
+    mut injury_event_writer: EventWriter<InjuryEvent>,
 ) {
 
     if frame_control.timer.finished() {
@@ -239,41 +425,104 @@ fn rabbit_movement(
             panic!("Cannot find the world map!");
         };
 
-        let mut rabbit_priority_vector: Vec<(Entity, RabbitPriorityMovement, i32, i32, Vec<(i32, i32)>)> = Vec::new();
-
-        for (rabbit_entity, rabbit, transform) in rabbit_query.iter() {
-
-            let x_range = match rabbit.location.0 {
-                0 => [rabbit.location.0, (rabbit.location.0 + 1)],
-                59 => [(rabbit.location.0 - 1),(rabbit.location.0)],
-                _ => [(rabbit.location.0 - 1),(rabbit.location.0 + 1)]
-            };
-
-            let z_range = match rabbit.location.1 {
-                0 => [(rabbit.location.1),(rabbit.location.1 + 1)],
-                59 => [(rabbit.location.1 - 1),(rabbit.location.1)],
-                _ => [(rabbit.location.1 - 1),(rabbit.location.1 + 1)]
-            };
+        let mut rabbit_priority_vector: Vec<RabbitMove> = Vec::new();
 
-            let mut possible_moves: Vec<(i32, i32)> = Vec::new();
+        // Rabbits are crepuscular: they feed up to full at dawn and dusk, only top up when
+        // hungry during the day, and rest through the night unless they are close to starving.
+        let day_phase = time_of_day.phase();
+        let social = &social_config.rabbit;
+        let needs = &needs_config.rabbit;
+        let mut rng = rand::thread_rng();
 
-            for x in x_range {
-                for z in z_range.clone() {
+        for (rabbit_entity, rabbit, _, _, memory) in rabbit_query.iter() {
 
-                    if let Ok(current_voxel) = voxel_query.get(world_map.map[x as usize][z as usize]) {
-                        if current_voxel.voxel_type == VoxelType::GrassVoxel || current_voxel.voxel_type == VoxelType::SandVoxel {
-                            possible_moves.push((x, z));
-                        }
-                    }
-                }
-            }
+            let possible_moves = movement_config.neighbours(world_map, rabbit.location);
 
             let mut rabbit_priority_movement = RabbitPriorityMovement::None;
             let mut x_direction = (std::i32::MAX-1) / 2;
             let mut z_direction = (std::i32::MAX-1) / 2;
+            let mut meal: Option<(Entity, PlantSpecies, f32)> = None;
 
-            if possible_moves.len() > 0 {
-                if rabbit.hunger >= rabbit.satisfaction_threshold && rabbit.thirst >= rabbit.satisfaction_threshold && rabbit.partner_in_range.len() > 0 && rabbit.age > 20 && rabbit.mating_cooldown == 0 {
+            let forage_threshold = match day_phase {
+                DayPhase::Dawn | DayPhase::Dusk => rabbit.full_threshold,
+                DayPhase::Day => rabbit.satisfaction_threshold,
+                DayPhase::Night => rabbit.satisfaction_threshold / 2.0,
+            };
+
+            let starving = rabbit.hunger < rabbit.satisfaction_threshold / 2.0 || rabbit.thirst < rabbit.satisfaction_threshold / 2.0;
+            // A rabbit carries on with its meal or drink until it has had its fill, the plant is grazed down or the water dries up.
+            let feeding = rabbit.feeding.filter(|feeding| match feeding {
+                Feeding::Grazing(plant_entity) => !needs.is_satiated(rabbit.hunger)
+                    && foliage_query.get(*plant_entity).is_ok_and(|plant| plant.is_edible() && plant.location == rabbit.location),
+                Feeding::Drinking(water_location) => !needs.is_satiated(rabbit.thirst)
+                    && world_map.voxel_type(*water_location) == Some(VoxelType::WaterVoxel)
+                    && movement_config.distance(rabbit.location, *water_location) == 1,
+            });
+            let tired = rabbit.energy < TIRED_ENERGY || (rabbit.resting && rabbit.energy < MAX_ENERGY);
+
+            if rabbit.alarmed > 0 && rabbit.energy > 0.0 {
+                // Bolt for the burrow and stay down until the danger has passed. Without a burrow, hide under
+                // the nearest trees, or run from the predator if there is no cover in sight.
+                let cover = world_map.tiles_within(rabbit.location, rabbit.sight())
+                    .filter(|&tile| world_map.voxel_type(tile).is_some_and(|voxel_type| voxel_type.gives_cover()))
+                    .min_by_key(|&tile| movement_config.distance(rabbit.location, tile));
+                match (rabbit.home.and_then(|home| burrow_query.get(home).ok()), cover) {
+                    (Some(burrow), _) if burrow.location != rabbit.location => {
+                        x_direction = burrow.location.0 - rabbit.location.0;
+                        z_direction = burrow.location.1 - rabbit.location.1;
+                        rabbit_priority_movement = RabbitPriorityMovement::Home;
+                    }
+                    (Some(_), _) => rabbit_priority_movement = RabbitPriorityMovement::Rest,
+                    (None, Some(cover)) if cover == rabbit.location => rabbit_priority_movement = RabbitPriorityMovement::Rest,
+                    (None, Some(cover)) => {
+                        x_direction = cover.0 - rabbit.location.0;
+                        z_direction = cover.1 - rabbit.location.1;
+                        rabbit_priority_movement = RabbitPriorityMovement::Flee;
+                    }
+                    (None, None) => {
+                        let source = rabbit.alarm_source.unwrap_or(rabbit.location);
+                        let away = ((rabbit.location.0 - source.0).signum(), (rabbit.location.1 - source.1).signum());
+                        let away = if away == (0, 0) { (1, 0) } else { away };
+                        x_direction = away.0 * rabbit.sight();
+                        z_direction = away.1 * rabbit.sight();
+                        rabbit_priority_movement = RabbitPriorityMovement::Flee;
+                    }
+                }
+            } else if let Some(feeding) = feeding {
+                match feeding {
+                    Feeding::Grazing(plant_entity) => {
+                        x_direction = 0;
+                        z_direction = 0;
+                        meal = take_bite(plant_entity, needs.bite_size, &foliage_query, &mut event_writer);
+                        rabbit_priority_movement = RabbitPriorityMovement::Food;
+                    }
+                    Feeding::Drinking(water_location) => {
+                        x_direction = water_location.0 - rabbit.location.0;
+                        z_direction = water_location.1 - rabbit.location.1;
+                        rabbit_priority_movement = RabbitPriorityMovement::Water;
+                    }
+                }
+            } else if rabbit.energy <= 0.0 || (tired && !starving) {
+                rabbit_priority_movement = RabbitPriorityMovement::Rest;
+            } else if let Some((_, mother, _, _, _)) = rabbit.mother
+                .filter(|_| rabbit.stage == LifeStage::Kit)
+                .and_then(|mother| rabbit_query.get(mother).ok()) {
+                // Kits can't forage yet, so they stay at their mother's side and suckle. Orphans have to fend for themselves.
+                x_direction = mother.location.0 - rabbit.location.0;
+                z_direction = mother.location.1 - rabbit.location.1;
+                rabbit_priority_movement = RabbitPriorityMovement::Follow;
+            } else if time_of_day.is_night() && rabbit.hunger >= forage_threshold && rabbit.thirst >= forage_threshold {
+                // Rabbits with a burrow head home to sleep; the rest sleep where they are.
+                match rabbit.home.and_then(|home| burrow_query.get(home).ok()) {
+                    Some(burrow) if burrow.location != rabbit.location => {
+                        x_direction = burrow.location.0 - rabbit.location.0;
+                        z_direction = burrow.location.1 - rabbit.location.1;
+                        rabbit_priority_movement = RabbitPriorityMovement::Home;
+                    }
+                    _ => rabbit_priority_movement = RabbitPriorityMovement::Rest,
+                }
+            } else if possible_moves.len() > 0 {
+                if rabbit.hunger >= rabbit.satisfaction_threshold && rabbit.thirst >= rabbit.satisfaction_threshold && rabbit.partner_in_range.len() > 0 && rabbit.stage.can_breed() && rabbit.mating_cooldown == 0 && season_cycle.season.breeding_allowed() {
                     //Look for partner
                     println!("Looking for partner");
 
@@ -282,7 +531,7 @@ fn rabbit_movement(
                     let mut closest_partner: Option<Entity> = None;
                     for partner_entity in rabbit.partner_in_range.clone() {
 
-                        let Ok((_, partner, _)) = rabbit_query.get(partner_entity) else {
+                        let Ok((_, partner, _, _, _)) = rabbit_query.get(partner_entity) else {
                             continue;
                         };
 
@@ -306,33 +555,38 @@ fn rabbit_movement(
                     }
 
                     
-                } else if (rabbit.hunger >= rabbit.full_threshold && rabbit.thirst >= rabbit.full_threshold) || (rabbit.plants_in_range.len() == 0 && rabbit.water_in_range.len() == 0) {
+                } else if (rabbit.hunger >= forage_threshold && rabbit.thirst >= forage_threshold) || (rabbit.plants_in_range.len() == 0 && rabbit.water_in_range.len() == 0 && memory.entries.is_empty()) {
 
                     rabbit_priority_movement = RabbitPriorityMovement::Random;
 
-                } else if rabbit.hunger < rabbit.full_threshold || rabbit.thirst < rabbit.full_threshold {
+                } else if rabbit.hunger < forage_threshold || rabbit.thirst < forage_threshold {
+                    let needed = if rabbit.hunger <= rabbit.thirst { ResourceKind::Food } else { ResourceKind::Water };
                     if rabbit.hunger <= rabbit.thirst && rabbit.plants_in_range.len() > 0 {
                         //Look for food
                         //println!("Looking for food");
 
                         rabbit_priority_movement = RabbitPriorityMovement::Food;
 
+                        // Favoured species are worth a longer walk, so distance is weighed against preference.
                         let mut closest_plant: Option<Entity> = None;
+                        let mut best_score = f32::MAX;
                         for plant_entity in rabbit.plants_in_range.clone() {
                             let Ok(plant) = foliage_query.get(plant_entity) else {
                                 continue;
                             };
                             let x_direction_temp = plant.location.0 - rabbit.location.0;
                             let z_direction_temp = plant.location.1 - rabbit.location.1;
+                            let score = (x_direction_temp.abs() + z_direction_temp.abs() + 1) as f32 / plant.species.rabbit_preference();
 
-                            if x_direction_temp.abs() + z_direction_temp.abs() < x_direction.abs() + z_direction.abs() { 
+                            if score < best_score {
+                                best_score = score;
                                 x_direction = x_direction_temp;
                                 z_direction = z_direction_temp;
                                 closest_plant = Some(plant_entity);
                             }
                         }
                         if x_direction == 0 && z_direction == 0 {
-                            event_writer.send(FoliageConsumedEvent(closest_plant.unwrap()));
+                            meal = take_bite(closest_plant.unwrap(), needs.bite_size, &foliage_query, &mut event_writer);
                         }
                         
                         
@@ -340,12 +594,9 @@ fn rabbit_movement(
                         //Look for water
                         //println!("Looking for water");
 
-                        for voxel_entity in rabbit.water_in_range.clone() {
-                            let Ok(voxel) = voxel_query.get(voxel_entity) else {
-                                continue;
-                            };
-                            let x_direction_temp = voxel.location.0 - rabbit.location.0;
-                            let z_direction_temp = voxel.location.1 - rabbit.location.1;
+                        for water_location in rabbit.water_in_range.iter() {
+                            let x_direction_temp = water_location.0 - rabbit.location.0;
+                            let z_direction_temp = water_location.1 - rabbit.location.1;
 
                             if x_direction_temp.abs() + z_direction_temp.abs() < x_direction.abs() + z_direction.abs() {
                                 x_direction = x_direction_temp;
@@ -356,6 +607,14 @@ fn rabbit_movement(
                         rabbit_priority_movement = RabbitPriorityMovement::Water;
 
                         
+                    } else if let Some(remembered) = memory.nearest(needed, rabbit.location) {
+                        // Nothing in sight, so head back to the nearest place it was seen before.
+                        x_direction = remembered.0 - rabbit.location.0;
+                        z_direction = remembered.1 - rabbit.location.1;
+                        rabbit_priority_movement = match needed {
+                            ResourceKind::Food => RabbitPriorityMovement::Food,
+                            ResourceKind::Water => RabbitPriorityMovement::Water,
+                        };
                     } else {
                         //Walk randomly.
                         rabbit_priority_movement = RabbitPriorityMovement::Random;
@@ -365,7 +624,34 @@ fn rabbit_movement(
                 
             }
 
-            rabbit_priority_vector.push((rabbit_entity, rabbit_priority_movement, x_direction, z_direction, possible_moves));
+            // Idle rabbits that have strayed from their group drift back towards their kin and burrow.
+            if matches!(rabbit_priority_movement, RabbitPriorityMovement::Random) && social.enabled && rng.gen::<f32>() < social.cohesion {
+                let mut weighted_location = Vec2::ZERO;
+                let mut total_weight = 0.0;
+                for neighbour_entity in rabbit.neighbours_in_range.iter() {
+                    let Ok((_, neighbour, _, _, _)) = rabbit_query.get(*neighbour_entity) else {
+                        continue;
+                    };
+                    let weight = if rabbit.home.is_some() && neighbour.home == rabbit.home { social.kin_weight } else { 1.0 };
+                    weighted_location += Vec2::new(neighbour.location.0 as f32, neighbour.location.1 as f32) * weight;
+                    total_weight += weight;
+                }
+                if let Some(burrow) = rabbit.home.and_then(|home| burrow_query.get(home).ok()) {
+                    weighted_location += Vec2::new(burrow.location.0 as f32, burrow.location.1 as f32) * social.kin_weight;
+                    total_weight += social.kin_weight;
+                }
+                if total_weight > 0.0 {
+                    let centre = (weighted_location / total_weight).round();
+                    let centre = (centre.x as i32, centre.y as i32);
+                    if movement_config.distance(rabbit.location, centre) > social.spacing {
+                        x_direction = centre.0 - rabbit.location.0;
+                        z_direction = centre.1 - rabbit.location.1;
+                        rabbit_priority_movement = RabbitPriorityMovement::Group;
+                    }
+                }
+            }
+
+            rabbit_priority_vector.push((rabbit_entity, rabbit_priority_movement, x_direction, z_direction, meal));
 
 
             //Update the rabbit's nearby resources
@@ -373,180 +659,163 @@ fn rabbit_movement(
 
         }
 
-        for (rabbit_entity, rabbit_priority_movement, x_direction, z_direction, possible_moves) in rabbit_priority_vector {
-            match rabbit_priority_movement {
-                RabbitPriorityMovement::Partner => {
+        // Take turns in a random order so no rabbit always wins the race for a crowded tile.
+        rabbit_priority_vector.shuffle(&mut rand::thread_rng());
+        let mut occupancy = Occupancy::new(movement_config.tile_capacity, rabbit_query.iter().map(|(_, rabbit, _, _, _)| rabbit.location));
 
-                    let (rabbit_entity, mut rabbit, mut transform) = rabbit_query.get_mut(rabbit_entity).unwrap();
-
-                    
-                    if x_direction.abs() > z_direction.abs() {
-                        if x_direction > 0 {
-                            rabbit.location.0 += 1;
-                        } else if x_direction < 0 {
-                            rabbit.location.0 -= 1;
-                        }
-                        transform.translation = Vec3::new((rabbit.location.0 - 30) as f32, 0.25, (rabbit.location.1 - 30) as f32);
-                    } else {
-                        if z_direction > 0 {
-                            rabbit.location.1 += 1;
-                        } else if z_direction < 0 {
-                            rabbit.location.1 -= 1;
-                        }
-                        transform.translation = Vec3::new((rabbit.location.0 - 30) as f32, 0.25, (rabbit.location.1 - 30) as f32);
-                    }
-                }
+        for (rabbit_entity, rabbit_priority_movement, x_direction, z_direction, meal) in rabbit_priority_vector {
+            let Ok((_, mut rabbit, mut tween, mut history, _)) = rabbit_query.get_mut(rabbit_entity) else {
+                continue;
+            };
+            let previous_location = rabbit.location;
+            let resting = matches!(rabbit_priority_movement, RabbitPriorityMovement::Rest);
+            let at_home = rabbit.home
+                .and_then(|home| burrow_query.get(home).ok())
+                .is_some_and(|burrow| burrow.location == rabbit.location);
+            rabbit.in_burrow = at_home && resting;
+            rabbit.resting = resting;
+
+            let target = (previous_location.0 + x_direction, previous_location.1 + z_direction);
+            let mut feeding = None;
+            let tiles_moved = match rabbit_priority_movement {
+                RabbitPriorityMovement::Partner => move_towards(&mut rabbit, target, 1, world_map, &movement_config, &mut occupancy),
                 RabbitPriorityMovement::Food => {
-
-                    let (rabbit_entity, mut rabbit, mut transform) = rabbit_query.get_mut(rabbit_entity).unwrap();
-
                     if x_direction == 0 && z_direction == 0 {
-                        //despawn the plant and increase hunger
+                        //graze the plant and increase hunger
                         
-                        rabbit.hunger += 10;
-                    } else {
-                        if x_direction.abs() > z_direction.abs() {
-                            if x_direction > 0 {
-                                rabbit.location.0 += 1;
-                            } else if x_direction < 0 {
-                                rabbit.location.0 -= 1;
-                            }
-                            transform.translation = Vec3::new((rabbit.location.0 - 30) as f32, 0.25, (rabbit.location.1 - 30) as f32);
-                        } else {
-                            if z_direction > 0 {
-                                rabbit.location.1 += 1;
-                            } else if z_direction < 0 {
-                                rabbit.location.1 -= 1;
+                        if let Some((plant_entity, species, eaten)) = meal {
+                            rabbit.hunger = (rabbit.hunger + eaten * species.nutrition()).min(needs.max);
+                            rabbit.thirst = (rabbit.thirst + eaten * species.thirst_contribution()).min(needs.max);
+                            // Toxic plants poison the rabbit, costing it more than the meal was worth.
+                            if species.toxicity() > 0.0 {
+                                injury_event_writer.send(InjuryEvent(rabbit_entity, eaten * species.toxicity()));
                             }
-                            transform.translation = Vec3::new((rabbit.location.0 - 30) as f32, 0.25, (rabbit.location.1 - 30) as f32);
+                            feeding = Some(Feeding::Grazing(plant_entity));
                         }
+                        0
+                    } else {
+                        move_towards(&mut rabbit, target, 0, world_map, &movement_config, &mut occupancy)
                     }
                 }
                 RabbitPriorityMovement::Water => {
-                    let (rabbit_entity, mut rabbit, mut transform) = rabbit_query.get_mut(rabbit_entity).unwrap();
-                    
-                    if (x_direction.abs() == 1 && z_direction == 0) || (z_direction.abs() == 1 && x_direction == 0) {
-                        //despawn the plant and increase hunger
-                        
+                    if movement_config.distance((0, 0), (x_direction, z_direction)) == 1 {
                         //println!("Drinking Water!");
-                        rabbit.thirst += 10;
+                        rabbit.thirst = (rabbit.thirst + needs.drink_rate).min(needs.max);
+                        feeding = Some(Feeding::Drinking(target));
+                        0
                     } else {
-                        if x_direction.abs() > z_direction.abs() {
-                            if x_direction > 0 {
-                                rabbit.location.0 += 1;
-                            } else if x_direction < 0 {
-                                rabbit.location.0 -= 1;
-                            }
-                            transform.translation = Vec3::new((rabbit.location.0 - 30) as f32, 0.25, (rabbit.location.1 - 30) as f32);
-                        } else {
-                            if z_direction > 0 {
-                                rabbit.location.1 += 1;
-                            } else if z_direction < 0 {
-                                rabbit.location.1 -= 1;
-                            }
-                            transform.translation = Vec3::new((rabbit.location.0 - 30) as f32, 0.25, (rabbit.location.1 - 30) as f32);
-                        }
+                        move_towards(&mut rabbit, target, 1, world_map, &movement_config, &mut occupancy)
                     }
                 }
-                RabbitPriorityMovement::Random => {
-                    //Walk randomly
-                    let (rabbit_entity, mut rabbit, mut transform) = rabbit_query.get_mut(rabbit_entity).unwrap();
-                    
-                    walk_randomly(&mut rabbit, &mut transform, &possible_moves);
-                    update_rabbit_hunger_and_thirst(&mut rabbit, rabbit_entity, &mut commands);
-                }
-                RabbitPriorityMovement::None => {
-                    //No priority movement
+                RabbitPriorityMovement::Random => walk_randomly(&mut rabbit, world_map, &movement_config, &mut occupancy),
+                RabbitPriorityMovement::Group => move_towards(&mut rabbit, target, social.spacing, world_map, &movement_config, &mut occupancy),
+                RabbitPriorityMovement::Flee => move_towards(&mut rabbit, target, 0, world_map, &movement_config, &mut occupancy),
+                RabbitPriorityMovement::Home => move_towards(&mut rabbit, target, 0, world_map, &movement_config, &mut occupancy),
+                RabbitPriorityMovement::Follow => {
+                    if movement_config.distance(rabbit.location, target) <= 1 {
+                        rabbit.hunger = (rabbit.hunger + NURSING_GAIN).min(needs.max);
+                        rabbit.thirst = (rabbit.thirst + NURSING_GAIN).min(needs.max);
+                        0
+                    } else {
+                        move_towards(&mut rabbit, target, 1, world_map, &movement_config, &mut occupancy)
+                    }
                 }
+                //Resting in place, or nothing to do
+                RabbitPriorityMovement::Rest | RabbitPriorityMovement::None => 0,
+            };
+            // Anything else the rabbit does, such as bolting from a predator, interrupts its meal.
+            rabbit.feeding = feeding;
+
+            // Moving tires the rabbit; resting restores it. Hunger and thirst wear down every tick in `needs`.
+            if tiles_moved > 0 {
+                let position = rabbit.world_position(world_map);
+                tween.move_to(position, tiles_moved);
+                history.record(rabbit.location, movement_config.history_length);
+            } else if resting {
+                rabbit.energy = (rabbit.energy + REST_ENERGY_GAIN).min(MAX_ENERGY);
             }
         }
     }
 
 }
 
-fn update_rabbit_hunger_and_thirst(
-    rabbit: &mut Rabbit,
-    rabbit_entity: Entity,
-    commands: &mut Commands,
-) {
-    
-    //Update the rabbit's hunger and thirst
-    if rabbit.hunger == 0 {
-        commands.entity(rabbit_entity).despawn();
-    } else {
-        rabbit.hunger -= 1;
-    }
-
-    if rabbit.thirst == 0 {
-        commands.entity(rabbit_entity).despawn();
-    } else {
-        rabbit.thirst -= 1;
-    }
+/// Takes a bite from a plant, `bite_size` if it is fully grown. A bite of a sparse plant is worth less than a bite of a lush one.
+fn take_bite(
+    plant_entity: Entity,
+    bite_size: f32,
+    foliage_query: &Query<&Foliage>,
+    event_writer: &mut EventWriter<FoliageConsumedEvent>,
+) -> Option<(Entity, PlantSpecies, f32)> {
+    let plant = foliage_query.get(plant_entity).ok()?;
+    let eaten = plant.bite(bite_size);
+    event_writer.send(FoliageConsumedEvent(plant_entity, eaten));
+    Some((plant_entity, plant.species, eaten))
 }
 
     
 fn walk_randomly(
     rabbit: &mut Rabbit,
-    transform: &mut Transform,
-    possible_moves: &Vec<(i32, i32)>,
-) {
+    world_map: &WorldMap,
+    config: &MovementConfig,
+    occupancy: &mut Occupancy,
+) -> u32 {
     let mut rng: rand::prelude::ThreadRng = rand::thread_rng();
-    let random_index = rng.gen_range(0..possible_moves.len());
-    rabbit.location = possible_moves[random_index];
-    transform.translation = Vec3::new((rabbit.location.0 - 30) as f32, 0.25, (rabbit.location.1 - 30) as f32);
+    let mut tiles_moved = 0;
+    rabbit.gather_stride();
+    loop {
+        let moves: Vec<(i32, i32)> = config.neighbours(world_map, rabbit.location)
+            .into_iter()
+            .filter(|&tile| occupancy.has_room(world_map, tile))
+            .collect();
+        if moves.is_empty() || !rabbit.take_step(moves[rng.gen_range(0..moves.len())], world_map, config, occupancy) {
+            break;
+        }
+        tiles_moved += 1;
+    }
+    tiles_moved
+}
+
+/// Moves the rabbit as far as its stride allows towards `target`, stopping `stop_distance` moves short of it.
+fn move_towards(
+    rabbit: &mut Rabbit,
+    target: (i32, i32),
+    stop_distance: i32,
+    world_map: &WorldMap,
+    config: &MovementConfig,
+    occupancy: &mut Occupancy,
+) -> u32 {
+    let mut tiles_moved = 0;
+    rabbit.gather_stride();
+    while config.distance(rabbit.location, target) > stop_distance {
+        let Some(next) = step_towards(world_map, config, occupancy, rabbit.location, target) else {
+            break;
+        };
+        if !rabbit.take_step(next, world_map, config, occupancy) {
+            break;
+        }
+        tiles_moved += 1;
+    }
+    tiles_moved
 }
 
 pub fn update_rabbit_nearby_resources(
     frame_control: Res<FrameControl>,
-    mut rabbit_query: Query<(Entity, &mut Rabbit)>,
-    foliage_query: Query<(Entity, &Foliage)>,
-    voxel_query: Query<(Entity, &Voxel)>,
+    mut rabbit_query: Query<(Entity, &mut Rabbit, &mut ResourceMemory)>,
+    foliage_query: Query<&Foliage>,
+    world_map_query: Query<&WorldMap>,
     mut event_writer: EventWriter<UpdateNearbyResourcesEvent>,
 ) {
     if frame_control.timer.finished() {
+        let Ok(world_map) = world_map_query.get_single() else {
+            return;
+        };
         
-        for (rabbit_entity, mut rabbit) in rabbit_query.iter_mut() {
-            let rabbit_x = rabbit.location.0;
-            let rabbit_z = rabbit.location.1;
-
-            rabbit.plants_in_range = Vec::new();
-            rabbit.water_in_range = Vec::new();
-
-            for (i, (f_entity, foliage)) in foliage_query.iter().enumerate() {
-                if foliage.consumed {
-                    continue;
-                }
-                if (
-                    rabbit_x - (rabbit.sight_distance as i32) <= foliage.location.0 
-                    && rabbit_x + (rabbit.sight_distance as i32) >= foliage.location.0
-                ) 
-                && (
-                    rabbit_z - (rabbit.sight_distance as i32) <= foliage.location.1 
-                    && rabbit_z + (rabbit.sight_distance as i32) >= foliage.location.1
-                ) {
-                    //println!("fol{}: {:?}", i, foliage.location);
-                    rabbit.plants_in_range.push(f_entity);
-                }
-            }
-            
-            for (j, (v_entity, voxel)) in voxel_query.iter().enumerate() {
-                if voxel.voxel_type == VoxelType::WaterVoxel {
-                    if (
-                        rabbit_x - (rabbit.sight_distance as i32) <= voxel.location.0 
-                        && rabbit_x + (rabbit.sight_distance as i32) >= voxel.location.0
-                    ) 
-                    && (
-                        rabbit_z - (rabbit.sight_distance as i32) <= voxel.location.1 
-                        && rabbit_z + (rabbit.sight_distance as i32) >= voxel.location.1
-                    ) {
-                        //println!("vox{}: {:?}", j, voxel.location);
-                        rabbit.water_in_range.push(v_entity);
-                    }
-                }
-            }
+        for (rabbit_entity, mut rabbit, mut memory) in rabbit_query.iter_mut() {
+            memory.age();
+            scan_nearby_resources(&mut rabbit, &mut memory, world_map, &foliage_query);
 
             // I need to figure out a way to query all other rabbits in a scene for this scenario.
-            if rabbit.age > 20 {
+            if rabbit.stage.can_breed() {
                 //event_writer.send(UpdateNearbyResourcesEvent(rabbit_entity));
             }
 
@@ -568,60 +837,96 @@ pub fn update_rabbit_nearby_resources(
     }
 }
 
-fn update_rabbit_partner_list(
+fn scan_nearby_resources(
+    rabbit: &mut Rabbit,
+    memory: &mut ResourceMemory,
+    world_map: &WorldMap,
+    foliage_query: &Query<&Foliage>,
+) {
+    let sight_distance = rabbit.sight();
+
+    rabbit.plants_in_range = world_map.foliage_within(rabbit.location, sight_distance)
+        .into_iter()
+        .filter(|f_entity| foliage_query.get(*f_entity).is_ok_and(|foliage| foliage.is_edible()))
+        .collect();
+    rabbit.water_in_range = world_map.water_within(rabbit.location, sight_distance);
+
+    let plant_locations: Vec<(i32, i32)> = rabbit.plants_in_range.iter()
+        .filter_map(|f_entity| foliage_query.get(*f_entity).ok().map(|foliage| foliage.location))
+        .collect();
+    memory.observe(rabbit.location, sight_distance, ResourceKind::Food, &plant_locations);
+    memory.observe(rabbit.location, sight_distance, ResourceKind::Water, &rabbit.water_in_range);
+}
+
+/// Rescans a rabbit's surroundings straight away, e.g. after the terrain under it was edited.
+fn refresh_requested_nearby_resources(
+    mut events: EventReader<UpdateNearbyResourcesEvent>,
+    mut rabbit_query: Query<(&mut Rabbit, &mut ResourceMemory)>,
+    foliage_query: Query<&Foliage>,
+    world_map_query: Query<&WorldMap>,
+) {
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+
+    for UpdateNearbyResourcesEvent(rabbit_entity) in events.read() {
+        let Ok((mut rabbit, mut memory)) = rabbit_query.get_mut(*rabbit_entity) else {
+            continue;
+        };
+        scan_nearby_resources(&mut rabbit, &mut memory, world_map, &foliage_query);
+    }
+}
+
+fn update_rabbit_neighbours(
     frame_control: Res<FrameControl>,
-    mut rabbit_resource: ResMut<RabbitResource>,
+    rabbit_resource: Res<RabbitResource>,
     mut rabbit_query: Query<(Entity, &mut Rabbit)>,
-    mut events: EventReader<UpdateNearbyResourcesEvent>,
 ) {
 
     if frame_control.timer.finished() {
-        if rabbit_resource.rabbits.len() as usize > 200 {
+        if rabbit_resource.rabbits.len() > 200 {
             return;
         }
 
-        for rabbit_entity in rabbit_resource.rabbits.iter_mut() {
+        for rabbit_entity in rabbit_resource.rabbits.iter() {
 
             let Ok((_, rabbit)) = rabbit_query.get(*rabbit_entity) else {
                 continue;
             };
-    
-            if rabbit.age < 20 {
-                continue;
-            }
-    
-            //println!("Updating partner list for rabbit: {:?}", rabbit_entity);
-    
+
             let rabbit_x = rabbit.location.0;
             let rabbit_z = rabbit.location.1;
-    
+            let can_mate = rabbit.stage.can_breed();
+            let sight_distance = rabbit.sight();
+
+            let mut neighbours: Vec<Entity> = Vec::new();
             let mut available_rabbits: Vec<Entity> = Vec::new();
-    
-            for (partner_entity, partner) in rabbit_query.iter() {
-                if partner_entity == *rabbit_entity {
-                    continue;
-                }
-                if partner.age < 20 || partner.mating_cooldown > 0 {
+
+            for (neighbour_entity, neighbour) in rabbit_query.iter() {
+                if neighbour_entity == *rabbit_entity {
                     continue;
                 }
-    
+
                 if (
-                    rabbit_x - (rabbit.sight_distance as i32) <= partner.location.0 
-                    && rabbit_x + (rabbit.sight_distance as i32) >= partner.location.0
+                    rabbit_x - sight_distance <= neighbour.location.0 
+                    && rabbit_x + sight_distance >= neighbour.location.0
                 ) 
                 && (
-                    rabbit_z - (rabbit.sight_distance as i32) <= partner.location.1 
-                    && rabbit_z + (rabbit.sight_distance as i32) >= partner.location.1
+                    rabbit_z - sight_distance <= neighbour.location.1 
+                    && rabbit_z + sight_distance >= neighbour.location.1
                 ) {
-                    //println!("rabbit {:?} for partner rabbit: {:?}", rabbit.location, partner.location);
-                    available_rabbits.push(partner_entity);
+                    neighbours.push(neighbour_entity);
+                    if can_mate && neighbour.stage.can_breed() && neighbour.mating_cooldown == 0 {
+                        available_rabbits.push(neighbour_entity);
+                    }
                 }
             }
-    
+
             let Ok((_, mut rabbit)) = rabbit_query.get_mut(*rabbit_entity) else {
                 continue;
             };
-    
+
+            rabbit.neighbours_in_range = neighbours;
             rabbit.partner_in_range = available_rabbits;
         }
     }
@@ -629,35 +934,47 @@ fn update_rabbit_partner_list(
 
 
 fn rabbit_age_tick(
-    mut commands: Commands,
     frame_control: Res<FrameControl>,
-    mut rabbit_resource: ResMut<RabbitResource>,
-    mut rabbit_query: Query<(Entity, &mut Rabbit)>,
+    life_config: Res<LifeConfig>,
+    mortality_model: Res<MortalityModel>,
+    mut survival_stats: ResMut<SurvivalStats>,
+    mut rabbit_query: Query<(&mut Rabbit, &mut Health)>,
     mut local_counter: Local<RabbitAgeLocalCounter>,
 ) {
     if frame_control.timer.finished() {
         local_counter.counter += 1;
         if local_counter.counter >= 5 {
-            for (entity, mut rabbit) in rabbit_query.iter_mut() {
-                rabbit.age += 1;
+            let mut rng = rand::thread_rng();
+            for (mut rabbit, mut health) in rabbit_query.iter_mut() {
                 if rabbit.mating_cooldown > 0 {
                     rabbit.mating_cooldown -= 1;
                 }
-                if rabbit.age >= 50 {
-                    let mut rng = rand::thread_rng();
-                    if rabbit.age < 100 {
-                        let random_number = rng.gen_range(0..(100 - rabbit.age));
-                        if random_number == 0 {
-                            rabbit_resource.rabbits.retain(|&x| x != entity);
-                            commands.entity(entity).despawn();
-                        }
-                    } else {
-                        rabbit_resource.rabbits.retain(|&x| x != entity);
-                        commands.entity(entity).despawn();
-                    }
+                // The roll is for the age the rabbit has reached; only survivors grow a year older.
+                let dies = mortality_model.dies(rabbit.age, &mut rng);
+                survival_stats.record(rabbit.age, dies);
+                if dies {
+                    health.kill();
+                } else {
+                    let age = rabbit.age + 1;
+                    rabbit.set_age(age, &life_config);
                 }
             }
             local_counter.counter = 0;
         }
     }
-}
\ No newline at end of file
+}
+
+fn rabbit_droppings(
+    frame_control: Res<FrameControl>,
+    rabbit_query: Query<&Rabbit>,
+    mut soil_event_writer: EventWriter<FertiliseSoilEvent>,
+) {
+    if frame_control.timer.finished() {
+        let mut rng = rand::thread_rng();
+        for rabbit in rabbit_query.iter() {
+            if rng.gen::<f32>() < DROPPING_CHANCE {
+                soil_event_writer.send(FertiliseSoilEvent(rabbit.location, DROPPING_NUTRIENTS));
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{disease::DiseaseConfig, fish::FishConfig, hawk::HawkConfig, life::LifeConfig, map_image::MapImageFormat, mortality::MortalityModel, movement::MovementConfig, needs::NeedsConfig, social::SocialConfig};
+
+/// Where the terrain of a new world comes from.
+#[derive(Deserialize, Clone, Debug)]
+pub enum TerrainSource {
+    Perlin { seed: u32 },
+    Image { path: String, format: MapImageFormat },
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MapExport {
+    pub path: String,
+    pub format: MapImageFormat,
+}
+
+/// Settings for one simulation run, read from the RON file passed with `--scenario <path>`.
+///
+/// Any field missing from the file keeps its default value.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Scenario {
+    pub world_width: i32,
+    pub world_height: i32,
+    pub terrain: TerrainSource,
+    /// Writes the generated terrain to an image once the world has been built.
+    pub export_map: Option<MapExport>,
+    /// Length of a full day and night in simulation ticks.
+    pub day_length: u32,
+    /// Length of each of the four seasons in simulation ticks.
+    pub season_length: u32,
+    /// Average number of ticks between weather changes.
+    pub weather_change_interval: u32,
+    pub movement: MovementConfig,
+    pub social: SocialConfig,
+    pub disease: DiseaseConfig,
+    /// Ages at which rabbits reach each life stage.
+    pub rabbit_life: LifeConfig,
+    /// How likely rabbits are to die of old age at each age.
+    pub rabbit_mortality: MortalityModel,
+    pub needs: NeedsConfig,
+    pub hawks: HawkConfig,
+    pub fish: FishConfig,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Scenario {
+            world_width: 60,
+            world_height: 60,
+            terrain: TerrainSource::Perlin { seed: Scenario::default_seed() },
+            export_map: None,
+            day_length: 400,
+            season_length: 1600,
+            weather_change_interval: 300,
+            movement: MovementConfig::default(),
+            social: SocialConfig::default(),
+            disease: DiseaseConfig::default(),
+            rabbit_life: LifeConfig::default(),
+            rabbit_mortality: MortalityModel::default(),
+            needs: NeedsConfig::default(),
+            hawks: HawkConfig::default(),
+            fish: FishConfig::default(),
+        }
+    }
+}
+
+impl Scenario {
+    pub fn default_seed() -> u32 {
+        5593487
+    }
+
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let Some(path) = args.iter().position(|arg| arg == "--scenario").and_then(|i| args.get(i + 1)) else {
+            return Scenario::default();
+        };
+
+        match std::fs::read_to_string(path) {
+            Ok(contents) => match ron::from_str(&contents) {
+                Ok(scenario) => scenario,
+                Err(error) => {
+                    println!("Failed to parse scenario {}: {}", path, error);
+                    Scenario::default()
+                }
+            },
+            Err(error) => {
+                println!("Failed to read scenario {}: {}", path, error);
+                Scenario::default()
+            }
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Scenario::from_args());
+}
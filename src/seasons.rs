@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use std::ops::Range;
+
+use crate::{frame_manager::FrameControl, scenario::Scenario};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Multiplier on how fast foliage grows back.
+    pub fn foliage_growth(&self) -> f32 {
+        match self {
+            Season::Spring => 1.5,
+            Season::Summer => 1.0,
+            Season::Autumn => 0.6,
+            Season::Winter => 0.15,
+        }
+    }
+
+    pub fn breeding_allowed(&self) -> bool {
+        *self != Season::Winter
+    }
+
+    pub fn litter_size(&self) -> Range<u32> {
+        match self {
+            Season::Spring => 2..5,
+            Season::Summer => 1..4,
+            Season::Autumn | Season::Winter => 1..2,
+        }
+    }
+
+    fn next(&self) -> Season {
+        match self {
+            Season::Spring => Season::Summer,
+            Season::Summer => Season::Autumn,
+            Season::Autumn => Season::Winter,
+            Season::Winter => Season::Spring,
+        }
+    }
+}
+
+/// Current season and how far into it the simulation is, in ticks.
+#[derive(Resource)]
+pub struct SeasonCycle {
+    pub season: Season,
+    pub tick: u32,
+    pub year: u32,
+    pub season_length: u32,
+}
+
+pub(super) fn plugin(app: &mut App) {
+    let season_length = app.world().resource::<Scenario>().season_length.max(1);
+    app
+        .insert_resource(SeasonCycle {
+            season: Season::Spring,
+            tick: 0,
+            year: 0,
+            season_length,
+        })
+        .add_systems(Update, advance_season);
+}
+
+fn advance_season(
+    frame_control: Res<FrameControl>,
+    mut season_cycle: ResMut<SeasonCycle>,
+) {
+    if frame_control.timer.finished() {
+        season_cycle.tick += 1;
+        if season_cycle.tick >= season_cycle.season_length {
+            season_cycle.tick = 0;
+            season_cycle.season = season_cycle.season.next();
+            if season_cycle.season == Season::Spring {
+                season_cycle.year += 1;
+            }
+            println!("Season changed to {:?}", season_cycle.season);
+        }
+    }
+}
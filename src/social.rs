@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    frame_manager::FrameControl,
+    rabbit::Rabbit,
+    scenario::Scenario,
+    world_setup::WorldMap,
+};
+
+/// Flocking settings for one species.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SocialBehaviour {
+    pub enabled: bool,
+    /// Chance per tick that an idle animal straying from its group heads back towards it.
+    pub cohesion: f32,
+    /// Tiles an animal may wander from the centre of its group before it feels alone.
+    pub spacing: i32,
+    /// How much more a relative, or the family burrow, pulls on the group centre than a stranger.
+    pub kin_weight: f32,
+    /// Tiles an alarm call carries from the animal that spotted a predator.
+    pub alarm_radius: i32,
+    /// Ticks an alarmed animal stays on edge.
+    pub alarm_duration: u32,
+}
+
+impl Default for SocialBehaviour {
+    fn default() -> Self {
+        SocialBehaviour {
+            enabled: true,
+            cohesion: 0.5,
+            spacing: 3,
+            kin_weight: 3.0,
+            alarm_radius: 6,
+            alarm_duration: 30,
+        }
+    }
+}
+
+/// Social behaviour of every species, set from the scenario's `social` section.
+#[derive(Resource, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct SocialConfig {
+    pub rabbit: SocialBehaviour,
+}
+
+/// Marks an animal that prey run from.
+#[derive(Component)]
+pub struct Predator;
+
+pub(super) fn plugin(app: &mut App) {
+    let social_config = app.world().resource::<Scenario>().social.clone();
+    app
+        .insert_resource(social_config)
+        .add_systems(Update, raise_rabbit_alarms);
+}
+
+/// Rabbits that see a predator stamp and call out, alarming every rabbit within earshot.
+fn raise_rabbit_alarms(
+    frame_control: Res<FrameControl>,
+    social_config: Res<SocialConfig>,
+    world_map_query: Query<&WorldMap>,
+    predator_query: Query<&Transform, With<Predator>>,
+    mut rabbit_query: Query<&mut Rabbit>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(world_map) = world_map_query.get_single() else {
+        return;
+    };
+    let behaviour = &social_config.rabbit;
+
+    let predators: Vec<(i32, i32)> = predator_query.iter().map(|transform| world_map.to_grid(transform.translation)).collect();
+    let mut alarms: Vec<((i32, i32), (i32, i32))> = Vec::new();
+
+    for mut rabbit in rabbit_query.iter_mut() {
+        rabbit.alarmed = rabbit.alarmed.saturating_sub(1);
+        if rabbit.in_burrow {
+            continue;
+        }
+        let sight_distance = rabbit.sight();
+        if let Some(&predator) = predators.iter().find(|predator| {
+            (predator.0 - rabbit.location.0).abs() <= sight_distance && (predator.1 - rabbit.location.1).abs() <= sight_distance
+        }) {
+            alarms.push((rabbit.location, predator));
+        }
+    }
+
+    // Without social behaviour only the rabbit that saw the predator reacts to it.
+    let alarm_radius = if behaviour.enabled { behaviour.alarm_radius } else { 0 };
+    for mut rabbit in rabbit_query.iter_mut() {
+        let heard = alarms.iter().find(|(caller, _)| {
+            (caller.0 - rabbit.location.0).abs() <= alarm_radius && (caller.1 - rabbit.location.1).abs() <= alarm_radius
+        });
+        if let Some(&(_, predator)) = heard {
+            rabbit.alarmed = behaviour.alarm_duration;
+            rabbit.alarm_source = Some(predator);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{
+    foliage::{Foliage, FoliageConsumedEvent},
+    frame_manager::FrameControl,
+    world_setup::{WorldMap, WorldMapDataSetEvent},
+};
+
+/// Adds nutrients to the soil at a location, e.g. from droppings or a corpse.
+#[derive(Event)]
+pub struct FertiliseSoilEvent(pub (i32, i32), pub f32);
+
+/// Fertility taken out of the soil for every unit of biomass grazed.
+const GRAZING_DEPLETION: f32 = 0.013;
+/// Fraction of the gap to the base fertility recovered per tick.
+const RECOVERY_RATE: f32 = 0.002;
+
+pub(super) fn plugin(app: &mut App) {
+    app
+        .add_event::<FertiliseSoilEvent>()
+        .add_systems(Update, (initialise_soil, deplete_grazed_soil, fertilise_soil, recover_soil_fertility));
+}
+
+fn initialise_soil(
+    mut events: EventReader<WorldMapDataSetEvent>,
+    mut world_map_query: Query<&mut WorldMap>,
+) {
+    for WorldMapDataSetEvent in events.read() {
+        let mut world_map = world_map_query.single_mut();
+        for x in 0..world_map.width {
+            for z in 0..world_map.height {
+                let Some(voxel_type) = world_map.voxel_type((x, z)) else {
+                    continue;
+                };
+                world_map.set_fertility((x, z), voxel_type.base_fertility());
+            }
+        }
+    }
+}
+
+fn deplete_grazed_soil(
+    mut events: EventReader<FoliageConsumedEvent>,
+    foliage_query: Query<&Foliage>,
+    mut world_map_query: Query<&mut WorldMap>,
+) {
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+    for FoliageConsumedEvent(foliage_entity, eaten) in events.read() {
+        let Ok(foliage) = foliage_query.get(*foliage_entity) else {
+            continue;
+        };
+        let fertility = world_map.fertility(foliage.location);
+        world_map.set_fertility(foliage.location, fertility - GRAZING_DEPLETION * eaten);
+    }
+}
+
+fn fertilise_soil(
+    mut events: EventReader<FertiliseSoilEvent>,
+    mut world_map_query: Query<&mut WorldMap>,
+) {
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+    for FertiliseSoilEvent(location, amount) in events.read() {
+        let fertility = world_map.fertility(*location);
+        world_map.set_fertility(*location, fertility + amount);
+    }
+}
+
+/// Slowly returns the soil of simulated chunks to the base fertility of its voxel type.
+fn recover_soil_fertility(
+    frame_control: Res<FrameControl>,
+    mut world_map_query: Query<&mut WorldMap>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    for chunk_index in 0..world_map.chunks.len() {
+        if !world_map.chunks[chunk_index].active {
+            continue;
+        }
+        let tiles: Vec<(i32, i32)> = world_map.chunk_tiles(chunk_index).collect();
+        for location in tiles {
+            let (Some(ticks), Some(voxel_type)) = (world_map.simulated_ticks(location), world_map.voxel_type(location)) else {
+                continue;
+            };
+            let fertility = world_map.fertility(location);
+            let rate = (RECOVERY_RATE * ticks as f32).min(1.0);
+            world_map.set_fertility(location, fertility + (voxel_type.base_fertility() - fertility) * rate);
+        }
+    }
+}
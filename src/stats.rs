@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::fmt::Write;
+
+use crate::{
+    carcass::Carcass,
+    disease::DiseaseState,
+    fish::Fish,
+    frame_manager::FrameControl,
+    hawk::Hawk,
+    mortality::{MortalityModel, SurvivalStats},
+    rabbit::Rabbit,
+};
+
+/// Population counts at one moment of the run.
+pub struct PopulationSample {
+    pub tick: u32,
+    pub rabbits: usize,
+    pub hawks: usize,
+    pub fish: usize,
+    pub carcasses: usize,
+    pub susceptible: usize,
+    pub exposed: usize,
+    pub infected: usize,
+    pub recovered: usize,
+}
+
+/// Population history of the run, sampled every `SAMPLE_INTERVAL` ticks.
+#[derive(Resource, Default)]
+pub struct PopulationStats {
+    pub tick: u32,
+    pub samples: Vec<PopulationSample>,
+}
+
+const SAMPLE_INTERVAL: u32 = 10;
+const STATS_EXPORT_PATH: &str = "population_stats.csv";
+
+pub(super) fn plugin(app: &mut App) {
+    app
+        .init_resource::<PopulationStats>()
+        .add_systems(Update, (record_population_stats, stats_ui));
+}
+
+fn record_population_stats(
+    frame_control: Res<FrameControl>,
+    mut population_stats: ResMut<PopulationStats>,
+    rabbit_query: Query<&Rabbit>,
+    hawk_query: Query<&Hawk>,
+    fish_query: Query<&Fish>,
+    carcass_query: Query<&Carcass>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    population_stats.tick += 1;
+    if !population_stats.tick.is_multiple_of(SAMPLE_INTERVAL) {
+        return;
+    }
+
+    let mut sample = PopulationSample {
+        tick: population_stats.tick,
+        rabbits: 0,
+        hawks: hawk_query.iter().count(),
+        fish: fish_query.iter().count(),
+        carcasses: carcass_query.iter().count(),
+        susceptible: 0,
+        exposed: 0,
+        infected: 0,
+        recovered: 0,
+    };
+    for rabbit in rabbit_query.iter() {
+        sample.rabbits += 1;
+        match rabbit.disease {
+            DiseaseState::Susceptible => sample.susceptible += 1,
+            DiseaseState::Exposed(_) => sample.exposed += 1,
+            DiseaseState::Infected(_) => sample.infected += 1,
+            DiseaseState::Recovered(_) => sample.recovered += 1,
+        }
+    }
+    population_stats.samples.push(sample);
+}
+
+fn stats_ui(
+    mut contexts: EguiContexts,
+    population_stats: Res<PopulationStats>,
+    mortality_model: Res<MortalityModel>,
+    survival_stats: Res<SurvivalStats>,
+) {
+    egui::Window::new("Stats").show(contexts.ctx_mut(), |ui| {
+        let Some(latest) = population_stats.samples.last() else {
+            ui.label("No samples yet");
+            return;
+        };
+        ui.label(format!("Rabbits: {}, hawks: {}, fish: {}, carcasses: {}", latest.rabbits, latest.hawks, latest.fish, latest.carcasses));
+        ui.label(format!(
+            "Susceptible {}, exposed {}, infected {}, recovered {}",
+            latest.susceptible, latest.exposed, latest.infected, latest.recovered,
+        ));
+        if ui.button("Export stats").clicked() {
+            match std::fs::write(STATS_EXPORT_PATH, stats_csv(&population_stats)) {
+                Ok(()) => println!("Exported population stats to {}", STATS_EXPORT_PATH),
+                Err(error) => println!("Failed to export population stats to {}: {}", STATS_EXPORT_PATH, error),
+            }
+        }
+        if ui.button("Export survival").clicked() {
+            survival_stats.export(&mortality_model);
+        }
+    });
+}
+
+fn stats_csv(population_stats: &PopulationStats) -> String {
+    let mut csv = String::from("tick,rabbits,hawks,fish,carcasses,susceptible,exposed,infected,recovered\n");
+    for sample in population_stats.samples.iter() {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{}",
+            sample.tick, sample.rabbits, sample.hawks, sample.fish, sample.carcasses, sample.susceptible, sample.exposed, sample.infected, sample.recovered,
+        );
+    }
+    csv
+}
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    frame_manager::FrameControl,
+    scenario::Scenario,
+    seasons::{Season, SeasonCycle},
+    world_setup::{ChunkLoader, VoxelType, WorldMap},
+};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum WeatherState {
+    Clear,
+    Rain,
+    Drought,
+}
+
+/// A land tile flooded by rain that turns back into `original` once it drains.
+pub struct Puddle {
+    pub location: (i32, i32),
+    pub original: VoxelType,
+    pub ticks_left: u32,
+}
+
+#[derive(Resource)]
+pub struct Weather {
+    pub state: WeatherState,
+    pub ticks_remaining: u32,
+    pub change_interval: u32,
+    pub puddles: Vec<Puddle>,
+    /// Shallow water tiles a drought turned into sand, refilled the next time it rains.
+    pub dried: Vec<(i32, i32)>,
+}
+
+impl Weather {
+    /// Stops tracking a tile whose terrain was changed by hand, so the weather doesn't undo the edit.
+    pub fn forget(&mut self, location: (i32, i32)) {
+        self.puddles.retain(|puddle| puddle.location != location);
+        self.dried.retain(|&tile| tile != location);
+    }
+}
+
+const PUDDLE_LIFETIME: u32 = 200;
+
+pub(super) fn plugin(app: &mut App) {
+    let change_interval = app.world().resource::<Scenario>().weather_change_interval.max(1);
+    app
+        .insert_resource(Weather {
+            state: WeatherState::Clear,
+            ticks_remaining: change_interval,
+            change_interval,
+            puddles: Vec::new(),
+            dried: Vec::new(),
+        })
+        .add_systems(Update, (change_weather, apply_weather_to_terrain, update_soil_moisture).chain());
+}
+
+fn change_weather(
+    frame_control: Res<FrameControl>,
+    season_cycle: Res<SeasonCycle>,
+    mut weather: ResMut<Weather>,
+) {
+    if frame_control.timer.finished() {
+        if weather.ticks_remaining > 0 {
+            weather.ticks_remaining -= 1;
+            return;
+        }
+
+        // Chances of (rain, drought); the rest of the time the sky is clear.
+        let (rain_chance, drought_chance) = match season_cycle.season {
+            Season::Spring => (0.4, 0.1),
+            Season::Summer => (0.15, 0.35),
+            Season::Autumn => (0.35, 0.15),
+            Season::Winter => (0.3, 0.1),
+        };
+
+        let mut rng = rand::thread_rng();
+        let roll = rng.gen::<f32>();
+        weather.state = if roll < rain_chance {
+            WeatherState::Rain
+        } else if roll < rain_chance + drought_chance {
+            WeatherState::Drought
+        } else {
+            WeatherState::Clear
+        };
+        weather.ticks_remaining = rng.gen_range(weather.change_interval / 2..=weather.change_interval * 3 / 2);
+        println!("Weather changed to {:?}", weather.state);
+    }
+}
+
+/// Floods low land into puddles while it rains, and dries shallow water into sand during droughts.
+fn apply_weather_to_terrain(
+    frame_control: Res<FrameControl>,
+    mut weather: ResMut<Weather>,
+    mut world_map_query: Query<&mut WorldMap>,
+    loader_query: Query<&Transform, With<ChunkLoader>>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+    // Keep the amount of change per tile the same whatever the size of the map.
+    let attempts = ((world_map.width * world_map.height) / 1200).max(1);
+    let occupied: Vec<(i32, i32)> = loader_query.iter().map(|transform| world_map.to_grid(transform.translation)).collect();
+
+    // Puddles drain in any weather, twice as fast in a drought, and not at all while it rains.
+    let drain = match weather.state {
+        WeatherState::Rain => 0,
+        WeatherState::Clear => 1,
+        WeatherState::Drought => 2,
+    };
+    let mut drained = Vec::new();
+    weather.puddles.retain_mut(|puddle| {
+        puddle.ticks_left = puddle.ticks_left.saturating_sub(drain);
+        if puddle.ticks_left == 0 {
+            drained.push((puddle.location, puddle.original));
+            false
+        } else {
+            true
+        }
+    });
+    for (location, original) in drained {
+        if world_map.voxel_type(location) == Some(VoxelType::WaterVoxel) {
+            world_map.set_voxel_type(location, original);
+        }
+    }
+
+    match weather.state {
+        WeatherState::Rain => {
+            for _ in 0..attempts {
+                // Plants and burrows that have spread onto a dried out tile keep it dry until they are gone.
+                let refillable = |tile: &(i32, i32)| {
+                    !occupied.contains(tile) && world_map.foliage_at(*tile).is_none() && world_map.burrow_at(*tile).is_none()
+                };
+                if let Some(index) = weather.dried.iter().position(refillable) {
+                    let location = weather.dried.swap_remove(index);
+                    world_map.set_voxel_type(location, VoxelType::WaterVoxel);
+                }
+
+                let location = (rng.gen_range(0..world_map.width), rng.gen_range(0..world_map.height));
+                let Some(original) = world_map.voxel_type(location) else {
+                    continue;
+                };
+                // Sand is low lying and floods anywhere; grass and forest only flood next to existing water.
+                let floods = match original {
+                    VoxelType::SandVoxel => true,
+                    VoxelType::GrassVoxel | VoxelType::ForestVoxel => world_map.has_water_within(location, 1),
+                    VoxelType::WaterVoxel => false,
+                };
+                if !floods || world_map.foliage_at(location).is_some() || world_map.burrow_at(location).is_some() || occupied.contains(&location) {
+                    continue;
+                }
+                world_map.set_voxel_type(location, VoxelType::WaterVoxel);
+                weather.puddles.push(Puddle { location, original, ticks_left: PUDDLE_LIFETIME });
+            }
+        }
+        WeatherState::Drought => {
+            for _ in 0..attempts {
+                let location = (rng.gen_range(0..world_map.width), rng.gen_range(0..world_map.height));
+                if world_map.voxel_type(location) != Some(VoxelType::WaterVoxel)
+                    || weather.puddles.iter().any(|puddle| puddle.location == location) {
+                    continue;
+                }
+                // Only the shallow edge of a body of water, next to dry land, dries out.
+                let shallow = world_map.tiles_within(location, 1)
+                    .any(|tile| tile != location && world_map.voxel_type(tile) != Some(VoxelType::WaterVoxel));
+                if shallow {
+                    world_map.set_voxel_type(location, VoxelType::SandVoxel);
+                    weather.dried.push(location);
+                }
+            }
+        }
+        WeatherState::Clear => {}
+    }
+}
+
+/// Relaxes the moisture of every simulated tile towards what its surroundings and the weather support.
+fn update_soil_moisture(
+    frame_control: Res<FrameControl>,
+    weather: Res<Weather>,
+    mut world_map_query: Query<&mut WorldMap>,
+) {
+    if !frame_control.timer.finished() {
+        return;
+    }
+    let Ok(mut world_map) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    let weather_factor = match weather.state {
+        WeatherState::Rain => 1.5,
+        WeatherState::Clear => 1.0,
+        WeatherState::Drought => 0.4,
+    };
+
+    for chunk_index in 0..world_map.chunks.len() {
+        if !world_map.chunks[chunk_index].active {
+            continue;
+        }
+        let tiles: Vec<(i32, i32)> = world_map.chunk_tiles(chunk_index).collect();
+        for location in tiles {
+            let Some(ticks) = world_map.simulated_ticks(location) else {
+                continue;
+            };
+            let target = if world_map.voxel_type(location) == Some(VoxelType::WaterVoxel) {
+                1.0
+            } else if world_map.has_water_within(location, 2) {
+                0.7 * weather_factor
+            } else {
+                0.4 * weather_factor
+            };
+            let moisture = world_map.moisture(location);
+            let rate = (0.02 * ticks as f32).min(1.0);
+            world_map.set_moisture(location, moisture + (target - moisture) * rate);
+        }
+    }
+}
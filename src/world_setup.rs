@@ -1,168 +1,546 @@
 use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
 use noise::{NoiseFn, Perlin};
 
-#[derive(Component, PartialEq, Clone)]
+use crate::{
+    frame_manager::FrameControl,
+    map_image::{self, MapImageFormat},
+    scenario::{Scenario, TerrainSource},
+};
+
+pub const CHUNK_SIZE: i32 = 16;
+
+#[derive(Component, PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(u8)]
 pub enum VoxelType {
     WaterVoxel,
     SandVoxel,
-    GrassVoxel
+    GrassVoxel,
+    /// Grassland under trees, where animals are hidden from anything flying overhead.
+    ForestVoxel,
 }
 
-#[derive(Component)]
-pub struct Voxel {
-    pub voxel_type: VoxelType,
-    pub location: (i32, i32),
+impl VoxelType {
+    /// Y coordinate of the centre of a voxel of this type.
+    pub fn centre_height(&self) -> f32 {
+        match self {
+            VoxelType::WaterVoxel => -1.0,
+            VoxelType::SandVoxel => -0.85,
+            VoxelType::GrassVoxel => -0.8,
+            VoxelType::ForestVoxel => -0.75,
+        }
+    }
+
+    /// Y coordinate of the top face of a voxel of this type.
+    pub fn surface_height(&self) -> f32 {
+        self.centre_height() + 0.5
+    }
+
+    /// Fertility the soil of this voxel type recovers towards when left alone.
+    pub fn base_fertility(&self) -> f32 {
+        match self {
+            VoxelType::WaterVoxel => 0.0,
+            VoxelType::SandVoxel => 0.2,
+            VoxelType::GrassVoxel => 0.6,
+            VoxelType::ForestVoxel => 0.7,
+        }
+    }
+
+    /// Chance that a seed landing on this voxel type takes root, before fertility is considered.
+    pub fn seed_germination(&self) -> f32 {
+        match self {
+            VoxelType::WaterVoxel => 0.0,
+            VoxelType::SandVoxel => 0.15,
+            VoxelType::GrassVoxel => 0.8,
+            VoxelType::ForestVoxel => 0.5,
+        }
+    }
+
+    /// Vertex colour used for this voxel type in the chunk meshes.
+    pub fn colour(&self) -> LinearRgba {
+        match self {
+            VoxelType::WaterVoxel => LinearRgba::rgb(0.0, 0.0, 1.0),
+            VoxelType::SandVoxel => LinearRgba::rgb(1.0, 1.0, 0.6),
+            VoxelType::GrassVoxel => LinearRgba::rgb(0.0, 1.0, 0.0),
+            VoxelType::ForestVoxel => LinearRgba::rgb(0.0, 0.4, 0.1),
+        }
+    }
+
+    /// Whether an animal on this voxel type is hidden from the air.
+    pub fn gives_cover(&self) -> bool {
+        *self == VoxelType::ForestVoxel
+    }
 }
 
-#[derive(Event)]
-pub struct VoxelsSpawnedEvent(pub Vec<Vec<Entity>>);
+/// A square block of terrain, `CHUNK_SIZE` tiles per side, stored as flat arrays indexed `x * CHUNK_SIZE + z`.
+pub struct TerrainChunk {
+    pub voxels: Vec<VoxelType>,
+    pub foliage: Vec<Option<Entity>>,
+    pub burrows: Vec<Option<Entity>>,
+    /// Soil moisture from 0.0 (parched) to 1.0 (waterlogged).
+    pub moisture: Vec<f32>,
+    /// Soil nutrients from 0.0 (exhausted) to 1.0, depleted by grazing and enriched by droppings and corpses.
+    pub fertility: Vec<f32>,
+    /// Algae on water tiles from 0.0 (clear) to 1.0 (choked), food for fish.
+    pub algae: Vec<f32>,
+    pub entity: Option<Entity>,
+    /// Set when the terrain changed and the chunk mesh must be rebuilt.
+    pub dirty: bool,
+    /// Whether anything in or next to this chunk needs simulating this tick.
+    pub active: bool,
+    /// Ticks that have passed while the chunk was inactive.
+    pub dormant_ticks: u32,
+    /// Dormant ticks handed to the simulation on the tick the chunk woke up.
+    pub catch_up_ticks: u32,
+}
 
+impl TerrainChunk {
+    fn new() -> Self {
+        let tile_count = (CHUNK_SIZE * CHUNK_SIZE) as usize;
+        TerrainChunk {
+            voxels: vec![VoxelType::GrassVoxel; tile_count],
+            foliage: vec![None; tile_count],
+            burrows: vec![None; tile_count],
+            moisture: vec![0.5; tile_count],
+            fertility: vec![VoxelType::GrassVoxel.base_fertility(); tile_count],
+            algae: vec![0.0; tile_count],
+            entity: None,
+            dirty: true,
+            active: false,
+            dormant_ticks: 0,
+            catch_up_ticks: 0,
+        }
+    }
+}
 
 #[derive(Event)]
 pub struct WorldMapDataSetEvent;
 
+/// Requests the current terrain be written to an image at the given path.
+#[derive(Event)]
+pub struct ExportWorldMapEvent(pub String, pub MapImageFormat);
+
 #[derive(Component)]
 pub struct WorldMap{
-    pub map: Vec<Vec<Entity>>,
     pub width: i32,
     pub height: i32,
+    pub chunks_x: i32,
+    pub chunks_z: i32,
+    pub chunks: Vec<TerrainChunk>,
 }
 
+impl WorldMap {
+    pub fn new(width: i32, height: i32) -> Self {
+        let chunks_x = (width + CHUNK_SIZE - 1) / CHUNK_SIZE;
+        let chunks_z = (height + CHUNK_SIZE - 1) / CHUNK_SIZE;
+        WorldMap {
+            width,
+            height,
+            chunks_x,
+            chunks_z,
+            chunks: (0..chunks_x * chunks_z).map(|_| TerrainChunk::new()).collect(),
+        }
+    }
+
+    pub fn in_bounds(&self, location: (i32, i32)) -> bool {
+        location.0 >= 0 && location.0 < self.width && location.1 >= 0 && location.1 < self.height
+    }
+
+    /// Returns (chunk index, tile index within the chunk) for a grid location.
+    fn index(&self, location: (i32, i32)) -> Option<(usize, usize)> {
+        if !self.in_bounds(location) {
+            return None;
+        }
+        let chunk = (location.0 / CHUNK_SIZE) * self.chunks_z + location.1 / CHUNK_SIZE;
+        let tile = (location.0 % CHUNK_SIZE) * CHUNK_SIZE + location.1 % CHUNK_SIZE;
+        Some((chunk as usize, tile as usize))
+    }
+
+    pub fn chunk_index(&self, location: (i32, i32)) -> Option<usize> {
+        self.index(location).map(|(chunk, _)| chunk)
+    }
+
+    /// Grid location of the first tile of a chunk.
+    pub fn chunk_origin(&self, chunk_index: usize) -> (i32, i32) {
+        let chunk_index = chunk_index as i32;
+        ((chunk_index / self.chunks_z) * CHUNK_SIZE, (chunk_index % self.chunks_z) * CHUNK_SIZE)
+    }
+
+    /// All in-bounds locations of one chunk.
+    pub fn chunk_tiles(&self, chunk_index: usize) -> impl Iterator<Item = (i32, i32)> {
+        let origin = self.chunk_origin(chunk_index);
+        let x_max = (origin.0 + CHUNK_SIZE).min(self.width);
+        let z_max = (origin.1 + CHUNK_SIZE).min(self.height);
+        (origin.0..x_max).flat_map(move |x| (origin.1..z_max).map(move |z| (x, z)))
+    }
+
+    pub fn voxel_type(&self, location: (i32, i32)) -> Option<VoxelType> {
+        self.index(location).map(|(chunk, tile)| self.chunks[chunk].voxels[tile])
+    }
+
+    pub fn set_voxel_type(&mut self, location: (i32, i32), voxel_type: VoxelType) {
+        let Some((chunk, tile)) = self.index(location) else {
+            return;
+        };
+        if self.chunks[chunk].voxels[tile] != voxel_type {
+            self.chunks[chunk].voxels[tile] = voxel_type;
+            // Side faces of tiles on a chunk edge depend on the neighbouring chunk too.
+            for neighbour in [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)] {
+                if let Some(neighbour_chunk) = self.chunk_index((location.0 + neighbour.0, location.1 + neighbour.1)) {
+                    self.chunks[neighbour_chunk].dirty = true;
+                }
+            }
+        }
+    }
+
+    pub fn is_walkable(&self, location: (i32, i32)) -> bool {
+        matches!(self.voxel_type(location), Some(VoxelType::GrassVoxel) | Some(VoxelType::SandVoxel) | Some(VoxelType::ForestVoxel))
+    }
+
+    /// Whether a fish can swim into a location; the reverse of `is_walkable`.
+    pub fn is_swimmable(&self, location: (i32, i32)) -> bool {
+        self.voxel_type(location) == Some(VoxelType::WaterVoxel)
+    }
+
+    pub fn foliage_at(&self, location: (i32, i32)) -> Option<Entity> {
+        self.index(location).and_then(|(chunk, tile)| self.chunks[chunk].foliage[tile])
+    }
+
+    pub fn set_foliage(&mut self, location: (i32, i32), foliage: Option<Entity>) {
+        if let Some((chunk, tile)) = self.index(location) {
+            self.chunks[chunk].foliage[tile] = foliage;
+        }
+    }
+
+    pub fn burrow_at(&self, location: (i32, i32)) -> Option<Entity> {
+        self.index(location).and_then(|(chunk, tile)| self.chunks[chunk].burrows[tile])
+    }
+
+    pub fn set_burrow(&mut self, location: (i32, i32), burrow: Option<Entity>) {
+        if let Some((chunk, tile)) = self.index(location) {
+            self.chunks[chunk].burrows[tile] = burrow;
+        }
+    }
+
+    pub fn moisture(&self, location: (i32, i32)) -> f32 {
+        self.index(location).map_or(0.0, |(chunk, tile)| self.chunks[chunk].moisture[tile])
+    }
+
+    pub fn set_moisture(&mut self, location: (i32, i32), moisture: f32) {
+        if let Some((chunk, tile)) = self.index(location) {
+            self.chunks[chunk].moisture[tile] = moisture.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn fertility(&self, location: (i32, i32)) -> f32 {
+        self.index(location).map_or(0.0, |(chunk, tile)| self.chunks[chunk].fertility[tile])
+    }
+
+    pub fn set_fertility(&mut self, location: (i32, i32), fertility: f32) {
+        if let Some((chunk, tile)) = self.index(location) {
+            self.chunks[chunk].fertility[tile] = fertility.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn algae(&self, location: (i32, i32)) -> f32 {
+        self.index(location).map_or(0.0, |(chunk, tile)| self.chunks[chunk].algae[tile])
+    }
+
+    pub fn set_algae(&mut self, location: (i32, i32), algae: f32) {
+        if let Some((chunk, tile)) = self.index(location) {
+            self.chunks[chunk].algae[tile] = algae.clamp(0.0, 1.0);
+        }
+    }
+
+    /// All in-bounds locations within a square of the given radius around `location`.
+    pub fn tiles_within(&self, location: (i32, i32), distance: i32) -> impl Iterator<Item = (i32, i32)> {
+        let x_min = (location.0 - distance).max(0);
+        let x_max = (location.0 + distance).min(self.width - 1);
+        let z_min = (location.1 - distance).max(0);
+        let z_max = (location.1 + distance).min(self.height - 1);
+        (x_min..=x_max).flat_map(move |x| (z_min..=z_max).map(move |z| (x, z)))
+    }
+
+    pub fn water_within(&self, location: (i32, i32), distance: i32) -> Vec<(i32, i32)> {
+        self.tiles_within(location, distance)
+            .filter(|tile| self.voxel_type(*tile) == Some(VoxelType::WaterVoxel))
+            .collect()
+    }
+
+    pub fn has_water_within(&self, location: (i32, i32), distance: i32) -> bool {
+        self.tiles_within(location, distance)
+            .any(|tile| self.voxel_type(tile) == Some(VoxelType::WaterVoxel))
+    }
+
+    pub fn foliage_within(&self, location: (i32, i32), distance: i32) -> Vec<Entity> {
+        self.tiles_within(location, distance)
+            .filter_map(|tile| self.foliage_at(tile))
+            .collect()
+    }
+
+    /// World space position of a grid location, with the world centred on the origin.
+    pub fn to_world(&self, location: (i32, i32), y: f32) -> Vec3 {
+        Vec3::new((location.0 - self.width / 2) as f32, y, (location.1 - self.height / 2) as f32)
+    }
+
+    pub fn to_grid(&self, translation: Vec3) -> (i32, i32) {
+        (translation.x.round() as i32 + self.width / 2, translation.z.round() as i32 + self.height / 2)
+    }
+
+    /// Number of ticks to simulate at `location` this tick, or `None` if its chunk is dormant.
+    pub fn simulated_ticks(&self, location: (i32, i32)) -> Option<u32> {
+        let chunk = &self.chunks[self.chunk_index(location)?];
+        if chunk.active {
+            Some(1 + chunk.catch_up_ticks)
+        } else {
+            None
+        }
+    }
+}
+
+/// Marks an entity whose presence keeps its own and the neighbouring chunks simulated.
+#[derive(Component)]
+pub struct ChunkLoader;
+
+/// Render entity holding the merged mesh of one terrain chunk.
+#[derive(Component)]
+pub struct TerrainChunkMesh;
+
+/// Single material shared by every chunk; the voxel colours come from the mesh vertex colours.
+#[derive(Resource)]
+pub struct TerrainMaterial(pub Handle<StandardMaterial>);
+
 pub(super) fn plugin(app: &mut App) {
     app
-        .add_event::<VoxelsSpawnedEvent>()
         .add_event::<WorldMapDataSetEvent>()
+        .add_event::<ExportWorldMapEvent>()
         .add_systems(Startup, spawn_world)
-        .add_systems(Update, set_world_map);
+        .add_systems(Update, (update_active_chunks, rebuild_dirty_chunks, export_world_map));
 }
 
 
 pub fn spawn_world(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    mut event_writer: EventWriter<VoxelsSpawnedEvent>,
+    scenario: Res<Scenario>,
+    mut event_writer: EventWriter<WorldMapDataSetEvent>,
+    mut export_event_writer: EventWriter<ExportWorldMapEvent>,
 ) {
-    let world_mesh = Mesh3d(meshes.add(Plane3d {
-        half_size: Vec2::new(30.0, 30.0),
+    commands.insert_resource(TerrainMaterial(materials.add(StandardMaterial {
+        base_color: Color::WHITE,
         ..default()
-    }));
-    let world_mesh_mat = MeshMaterial3d(
-        materials.add(StandardMaterial {
-            base_color: Color::linear_rgba(0.1, 0.9, 0.3, 0.0),
-            ..default()
-        })
-    );
-
+    })));
 
+    let world_map = match &scenario.terrain {
+        TerrainSource::Perlin { seed } => {
+            let mut world_map = WorldMap::new(scenario.world_width, scenario.world_height);
+            generate_perlin_terrain(&mut world_map, *seed);
+            world_map
+        }
+        TerrainSource::Image { path, format } => {
+            match map_image::load_terrain_image(path, *format) {
+                Ok(world_map) => world_map,
+                Err(error) => {
+                    println!("Failed to load map image {}: {}, falling back to Perlin terrain", path, error);
+                    let mut world_map = WorldMap::new(scenario.world_width, scenario.world_height);
+                    generate_perlin_terrain(&mut world_map, Scenario::default_seed());
+                    world_map
+                }
+            }
+        }
+    };
 
+    //Spawn World base.
     commands.spawn((
-        PointLight {
-            shadows_enabled: true,
-            intensity: 10_000_000.,
-            range: 1_000_000.0,
-            radius: 1_000_000.0,
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(0.0, 10.0, 0.0)),
+        world_map,
+        Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
+        Visibility::Visible,
     ));
 
-    //Spawn World base.
-    let mut world = commands.spawn((
-            WorldMap {
-                map: Vec::new(),
-                width: 60,
-                height: 60,
-            },
-            world_mesh,
-            world_mesh_mat,
-            Visibility::Hidden,
-            Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
-        )
-    );
-
-
-    let voxel = meshes.add(Cuboid {
-        half_size: Vec3::new(0.5,0.5,0.5),
-        ..default()
-    });
+    event_writer.send(WorldMapDataSetEvent);
 
-    let water_voxel_mat = materials.add(StandardMaterial {
-        base_color: Color::linear_rgb(0.0, 0.0,1.0),
-        ..default()
-    });
+    if let Some(export) = &scenario.export_map {
+        export_event_writer.send(ExportWorldMapEvent(export.path.clone(), export.format));
+    }
+}
 
-    let grass_voxel_mat = materials.add(StandardMaterial {
-        base_color: Color::linear_rgb(0.0, 1.0,0.0),
-        ..default()
-    });
+fn export_world_map(
+    mut events: EventReader<ExportWorldMapEvent>,
+    world_map_query: Query<&WorldMap>,
+) {
+    for ExportWorldMapEvent(path, format) in events.read() {
+        let Ok(world_map) = world_map_query.get_single() else {
+            continue;
+        };
+        match map_image::save_terrain_image(world_map, path, *format) {
+            Ok(()) => println!("Exported world map to {}", path),
+            Err(error) => println!("Failed to export world map to {}: {}", path, error),
+        }
+    }
+}
 
-    let sand_voxel_mat = materials.add(StandardMaterial {
-        base_color: Color::linear_rgb(1.0, 1.0,0.6),
-        ..default()
-    });
-
-    let perlin = Perlin::new(5593487);
-
-    let mut world_voxels: Vec<Vec<Entity>> = Vec::new();
-
-    world.with_children(|parent| {
-
-        //Spawn a bunch of cubes.
-        for i in -30..30 {
-            world_voxels.push(Vec::new());
-            for j in -30..30 {
-                
-                let p_value = perlin.get([(i as f64 + 30.)/10. , (j as f64 + 30.)/10. ]);
-
-                if p_value < -0.15 {
-                    world_voxels[(i + 30) as usize].push(parent.spawn((
-                        Voxel {voxel_type: VoxelType::WaterVoxel, location: (i + 30, j + 30)},
-                        Mesh3d(voxel.clone()),
-                        MeshMaterial3d(water_voxel_mat.clone()),
-                        Visibility::Visible,
-                        Transform::from_translation(Vec3::new(i as f32, -1., j as f32)),
-                    )).id());
-                } else if p_value >= -0.15 && p_value < 0.05 {
-                    world_voxels[(i + 30) as usize].push(parent.spawn((
-                        Voxel {voxel_type: VoxelType::SandVoxel, location: (i + 30, j + 30)},
-                        Mesh3d(voxel.clone()),
-                        MeshMaterial3d(sand_voxel_mat.clone()),
-                        Visibility::Visible,
-                        Transform::from_translation(Vec3::new(i as f32, -0.85, j as f32)),
-                    )).id());
-                } else {
-                    world_voxels[(i + 30) as usize].push(parent.spawn((
-                        Voxel {voxel_type: VoxelType::GrassVoxel, location: (i + 30, j + 30)},
-                        Mesh3d(voxel.clone()),
-                        MeshMaterial3d(grass_voxel_mat.clone()),
-                        Visibility::Visible,
-                        Transform::from_translation(Vec3::new(i as f32, -0.8, j as f32)),
-                    )).id());
+pub fn generate_perlin_terrain(world_map: &mut WorldMap, seed: u32) {
+    let perlin = Perlin::new(seed);
+
+    for x in 0..world_map.width {
+        for z in 0..world_map.height {
+            let p_value = perlin.get([x as f64 / 10., z as f64 / 10.]);
+
+            let voxel_type = if p_value < -0.15 {
+                VoxelType::WaterVoxel
+            } else if p_value < 0.05 {
+                VoxelType::SandVoxel
+            } else if p_value < 0.35 {
+                VoxelType::GrassVoxel
+            } else {
+                VoxelType::ForestVoxel
+            };
+            world_map.set_voxel_type((x, z), voxel_type);
+        }
+    }
+}
+
+/// Wakes chunks that contain a `ChunkLoader` (and their neighbours) and lets the others go dormant.
+pub fn update_active_chunks(
+    frame_control: Res<FrameControl>,
+    mut world_map_query: Query<&mut WorldMap>,
+    loader_query: Query<&Transform, With<ChunkLoader>>,
+) {
+    if frame_control.timer.finished() {
+        let Ok(mut world_map) = world_map_query.get_single_mut() else {
+            return;
+        };
+
+        let mut wanted = vec![false; world_map.chunks.len()];
+        for transform in loader_query.iter() {
+            let location = world_map.to_grid(transform.translation);
+            let chunk_x = location.0.clamp(0, world_map.width - 1) / CHUNK_SIZE;
+            let chunk_z = location.1.clamp(0, world_map.height - 1) / CHUNK_SIZE;
+            for x in (chunk_x - 1).max(0)..=(chunk_x + 1).min(world_map.chunks_x - 1) {
+                for z in (chunk_z - 1).max(0)..=(chunk_z + 1).min(world_map.chunks_z - 1) {
+                    wanted[(x * world_map.chunks_z + z) as usize] = true;
                 }
             }
         }
-    });
 
-    event_writer.send(VoxelsSpawnedEvent(world_voxels));
+        for (chunk, active) in world_map.chunks.iter_mut().zip(wanted) {
+            if active {
+                chunk.catch_up_ticks = if chunk.active { 0 } else { chunk.dormant_ticks };
+                chunk.dormant_ticks = 0;
+            } else {
+                chunk.catch_up_ticks = 0;
+                chunk.dormant_ticks += 1;
+            }
+            chunk.active = active;
+        }
+    }
 }
 
-fn set_world_map(
-    mut event_reader: EventReader<VoxelsSpawnedEvent>,
-    mut event_writer: EventWriter<WorldMapDataSetEvent>,
-    mut world_query: Query<(Entity, &mut WorldMap)>,
+fn rebuild_dirty_chunks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    terrain_material: Res<TerrainMaterial>,
+    mut world_map_query: Query<(Entity, &mut WorldMap)>,
 ) {
+    let Ok((world_entity, mut world_map)) = world_map_query.get_single_mut() else {
+        return;
+    };
+
+    for chunk_index in 0..world_map.chunks.len() {
+        if !world_map.chunks[chunk_index].dirty {
+            continue;
+        }
+        world_map.chunks[chunk_index].dirty = false;
+
+        let mesh = Mesh3d(meshes.add(build_chunk_mesh(&world_map, chunk_index)));
+
+        match world_map.chunks[chunk_index].entity {
+            Some(chunk_entity) => {
+                commands.entity(chunk_entity).insert(mesh);
+            }
+            None => {
+                let chunk_entity = commands.spawn((
+                    TerrainChunkMesh,
+                    mesh,
+                    MeshMaterial3d(terrain_material.0.clone()),
+                    Transform::default(),
+                    Visibility::Visible,
+                )).id();
+                commands.entity(world_entity).add_child(chunk_entity);
+                world_map.chunks[chunk_index].entity = Some(chunk_entity);
+            }
+        }
+    }
+}
+
+/// Merges the visible faces of every voxel in a chunk into one vertex coloured mesh.
+///
+/// Top faces are always emitted; side faces only where the neighbouring column is lower or
+/// missing, and bottom faces never, since the camera can't see under the world.
+fn build_chunk_mesh(world_map: &WorldMap, chunk_index: usize) -> Mesh {
+    let mut mesh_data = ChunkMeshData::default();
 
-    for active_event in event_reader.read() {
-        match active_event {
-            VoxelsSpawnedEvent(world_voxels) => {
-                
-                println!("Event received 2!");
-                world_query.single_mut().1.map = world_voxels.clone();
-                println!("Event sending!");
-                event_writer.send(WorldMapDataSetEvent);
-                println!("Event se t!");
+    for (x, z) in world_map.chunk_tiles(chunk_index) {
+        let Some(voxel_type) = world_map.voxel_type((x, z)) else {
+            continue;
+        };
+        let centre = world_map.to_world((x, z), voxel_type.centre_height());
+        let colour = voxel_type.colour();
+
+        for face in CUBOID_FACES {
+            let (normal, _, _) = face;
+            if normal == Vec3::NEG_Y {
+                continue;
+            }
+            if normal != Vec3::Y {
+                let neighbour = (x + normal.x as i32, z + normal.z as i32);
+                if world_map.voxel_type(neighbour)
+                    .is_some_and(|neighbour_type| neighbour_type.surface_height() >= voxel_type.surface_height()) {
+                    continue;
+                }
             }
+            mesh_data.push_face(centre, Vec3::splat(0.5), face, colour);
         }
     }
-}
\ No newline at end of file
+
+    mesh_data.into_mesh()
+}
+
+/// Face normals of a cuboid paired with two tangents whose cross product is the normal.
+const CUBOID_FACES: [(Vec3, Vec3, Vec3); 6] = [
+    (Vec3::X, Vec3::Y, Vec3::Z),
+    (Vec3::NEG_X, Vec3::Z, Vec3::Y),
+    (Vec3::Y, Vec3::Z, Vec3::X),
+    (Vec3::NEG_Y, Vec3::X, Vec3::Z),
+    (Vec3::Z, Vec3::X, Vec3::Y),
+    (Vec3::NEG_Z, Vec3::Y, Vec3::X),
+];
+
+#[derive(Default)]
+struct ChunkMeshData {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    colours: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl ChunkMeshData {
+    fn push_face(&mut self, centre: Vec3, half_size: Vec3, (normal, u, v): (Vec3, Vec3, Vec3), colour: LinearRgba) {
+        let start = self.positions.len() as u32;
+        for corner in [normal - u - v, normal + u - v, normal + u + v, normal - u + v] {
+            self.positions.push((centre + corner * half_size).to_array());
+            self.normals.push(normal.to_array());
+            self.colours.push(colour.to_f32_array());
+        }
+        self.indices.extend_from_slice(&[start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+
+    fn into_mesh(self) -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colours)
+            .with_inserted_indices(Indices::U32(self.indices))
+    }
+}